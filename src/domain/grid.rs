@@ -0,0 +1,245 @@
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub(crate) fn new(rows: Vec<Vec<T>>) -> Result<Self, RaggedGridError> {
+        let height = rows.len();
+        let width = rows.first().map(Vec::len).unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * height);
+
+        for (row_index, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(RaggedGridError::new(row_index, width, row.len()));
+            }
+            cells.extend(row);
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub(crate) fn width(&self) -> usize {
+        self.width
+    }
+
+    pub(crate) fn height(&self) -> usize {
+        self.height
+    }
+
+    pub(crate) fn map<U>(self, f: impl FnMut(T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.into_iter().map(f).collect(),
+        }
+    }
+
+    pub(crate) fn get(&self, position: GridPosition) -> Option<&T> {
+        self.index_of(position).map(|index| &self.cells[index])
+    }
+
+    pub(crate) fn get_mut(&mut self, position: GridPosition) -> Option<&mut T> {
+        self.index_of(position).map(|index| &mut self.cells[index])
+    }
+
+    pub(crate) fn contains(&self, position: GridPosition) -> bool {
+        position.row < self.height && position.col < self.width
+    }
+
+    /// All positions in row-major order.
+    pub(crate) fn positions(&self) -> impl Iterator<Item = GridPosition> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| GridPosition::new(row, col)))
+    }
+
+    /// All cells in row-major order, paired with their position.
+    pub(crate) fn cells(&self) -> impl Iterator<Item = (GridPosition, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Positions adjacent to `position` that lie within the grid.
+    pub(crate) fn neighbours(
+        &self,
+        position: GridPosition,
+        adjacency: Adjacency,
+    ) -> impl Iterator<Item = GridPosition> + '_ {
+        adjacency
+            .offsets()
+            .iter()
+            .filter_map(move |(row_offset, col_offset)| {
+                let neighbour = GridPosition::new(
+                    position.row.checked_add_signed(*row_offset)?,
+                    position.col.checked_add_signed(*col_offset)?,
+                );
+                self.contains(neighbour).then_some(neighbour)
+            })
+    }
+
+    fn index_of(&self, position: GridPosition) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.row * self.width + position.col)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Renders one line per row, formatting each cell with `render_cell`.
+    pub(crate) fn display_with(&self, render_cell: impl Fn(GridPosition, &T) -> String) -> String {
+        self.cells()
+            .chunks(self.width.max(1))
+            .into_iter()
+            .map(|row| {
+                row.map(|(position, cell)| render_cell(position, cell))
+                    .join("")
+            })
+            .join("\n")
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.display_with(|_position, cell| cell.to_string())
+        )
+    }
+}
+
+#[derive(derive_new::new, Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct GridPosition {
+    row: usize,
+    col: usize,
+}
+
+impl GridPosition {
+    pub(crate) fn row(&self) -> usize {
+        self.row
+    }
+
+    pub(crate) fn col(&self) -> usize {
+        self.col
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Adjacency {
+    /// Up, down, left and right.
+    FourWay,
+    /// The four-way neighbours plus the diagonals.
+    EightWay,
+}
+
+impl Adjacency {
+    fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Adjacency::FourWay => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+            Adjacency::EightWay => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new)]
+#[error("grid row {row} has {found} cells, expected {expected}")]
+pub(crate) struct RaggedGridError {
+    row: usize,
+    expected: usize,
+    found: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn test_grid() -> Grid<u8> {
+        Grid::new(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap()
+    }
+
+    #[test]
+    fn takes_dimensions_from_rows() {
+        let grid = test_grid();
+        assert_that(&grid.contains(GridPosition::new(1, 2))).is_true();
+        assert_that(&grid.contains(GridPosition::new(2, 0))).is_false();
+        assert_that(&grid.contains(GridPosition::new(0, 3))).is_false();
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        assert_that(&Grid::new(vec![vec![1, 2, 3], vec![4, 5]]).is_err()).is_true();
+    }
+
+    #[test]
+    fn iterates_cells_in_row_major_order() {
+        let cells: Vec<(GridPosition, u8)> = test_grid()
+            .cells()
+            .map(|(position, value)| (position, *value))
+            .collect();
+        assert_that(&cells).is_equal_to(vec![
+            (GridPosition::new(0, 0), 1),
+            (GridPosition::new(0, 1), 2),
+            (GridPosition::new(0, 2), 3),
+            (GridPosition::new(1, 0), 4),
+            (GridPosition::new(1, 1), 5),
+            (GridPosition::new(1, 2), 6),
+        ]);
+    }
+
+    #[test]
+    fn finds_four_way_neighbours_within_bounds() {
+        let grid = test_grid();
+        let neighbours: Vec<GridPosition> = grid
+            .neighbours(GridPosition::new(0, 0), Adjacency::FourWay)
+            .collect();
+        assert_that(&neighbours)
+            .is_equal_to(vec![GridPosition::new(1, 0), GridPosition::new(0, 1)]);
+    }
+
+    #[test]
+    fn finds_eight_way_neighbours_within_bounds() {
+        let grid = test_grid();
+        let neighbours: Vec<GridPosition> = grid
+            .neighbours(GridPosition::new(0, 1), Adjacency::EightWay)
+            .collect();
+        assert_that(&neighbours).is_equal_to(vec![
+            GridPosition::new(0, 0),
+            GridPosition::new(0, 2),
+            GridPosition::new(1, 0),
+            GridPosition::new(1, 1),
+            GridPosition::new(1, 2),
+        ]);
+    }
+
+    #[test]
+    fn displays_rows_on_separate_lines() {
+        assert_that(&test_grid().to_string()).is_equal_to("123\n456".to_string());
+    }
+}
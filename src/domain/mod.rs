@@ -1 +1,2 @@
+pub(crate) mod grid;
 pub(crate) mod solution_executor;
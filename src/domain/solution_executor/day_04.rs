@@ -1,70 +1,137 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+
 use itertools::Itertools;
 
+use crate::domain::grid::{Grid, GridPosition};
 use crate::domain::solution_executor::SolutionExecutor;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct BingoGame {
     draw_order: Vec<u8>,
     boards: Vec<BingoBoard>,
+    number_index: NumberIndex,
 }
 
 impl BingoGame {
     pub(crate) fn new(draw_order: Vec<u8>, boards: Vec<Vec<Vec<u8>>>) -> Self {
+        let boards: Vec<BingoBoard> = boards.into_iter().map(BingoBoard::new).collect();
+        let number_index = NumberIndex::new(&boards);
         BingoGame {
             draw_order,
-            boards: boards.into_iter().map(BingoBoard::new).collect(),
+            boards,
+            number_index,
         }
     }
 }
 
+/// Where each number appears across all boards, so a draw only visits the cells it marks.
+#[derive(Debug, Clone)]
+struct NumberIndex {
+    occurrences: HashMap<u8, Vec<(usize, GridPosition)>>,
+}
+
+impl NumberIndex {
+    fn new(boards: &[BingoBoard]) -> Self {
+        let mut occurrences: HashMap<u8, Vec<(usize, GridPosition)>> = HashMap::new();
+        boards.iter().enumerate().for_each(|(board_index, board)| {
+            board.cells.cells().for_each(|(position, cell)| {
+                occurrences
+                    .entry(cell.value)
+                    .or_default()
+                    .push((board_index, position))
+            })
+        });
+        NumberIndex { occurrences }
+    }
+
+    fn occurrences(&self, number: u8) -> &[(usize, GridPosition)] {
+        self.occurrences
+            .get(&number)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct BingoBoard {
-    rows: Vec<Vec<BoardCell>>,
+    cells: Grid<BoardCell>,
+    row_marks: Vec<usize>,
+    column_marks: Vec<usize>,
+    main_diagonal_marks: usize,
+    anti_diagonal_marks: usize,
+    corner_marks: usize,
+    marked_count: usize,
+    unmarked_sum: usize,
 }
 
 impl BingoBoard {
     pub(crate) fn new(rows: Vec<Vec<u8>>) -> Self {
+        let cells = Grid::new(rows)
+            .expect("bingo board rows are not all the same width")
+            .map(|value| BoardCell::new(value, MarkedOrUnmarked::Unmarked));
         BingoBoard {
-            rows: rows
-                .into_iter()
-                .map(|row| {
-                    row.into_iter()
-                        .map(|value| BoardCell::new(value, MarkedOrUnmarked::Unmarked))
-                        .collect()
-                })
-                .collect(),
+            row_marks: vec![0; cells.height()],
+            column_marks: vec![0; cells.width()],
+            main_diagonal_marks: 0,
+            anti_diagonal_marks: 0,
+            corner_marks: 0,
+            marked_count: 0,
+            unmarked_sum: cells.values().map(|cell| cell.value as usize).sum(),
+            cells,
         }
     }
 
-    fn unmarked_numbers(&self) -> Vec<u8> {
-        self.rows
-            .iter()
-            .flatten()
-            .filter(|cell| !cell.is_marked())
-            .map(|cell| cell.value)
-            .collect()
+    /// Marks the cell at `position`, returning false if it was already marked.
+    fn mark(&mut self, position: GridPosition) -> bool {
+        let cell = self.cells.get_mut(position).expect("cell does not exist");
+        if cell.is_marked() {
+            return false;
+        }
+        cell.state = MarkedOrUnmarked::Marked;
+        self.unmarked_sum -= cell.value as usize;
+        self.row_marks[position.row()] += 1;
+        self.column_marks[position.col()] += 1;
+        self.marked_count += 1;
+        if self.on_main_diagonal(position) {
+            self.main_diagonal_marks += 1;
+        }
+        if self.on_anti_diagonal(position) {
+            self.anti_diagonal_marks += 1;
+        }
+        if self.is_corner(position) {
+            self.corner_marks += 1;
+        }
+        true
     }
 
-    fn rows(&self) -> &Vec<Vec<BoardCell>> {
-        &self.rows
+    fn is_square(&self) -> bool {
+        self.cells.width() == self.cells.height()
     }
 
-    fn cell_at_position(&self, row: usize, column: usize) -> &BoardCell {
-        self.rows.get(row).unwrap().get(column).unwrap()
+    fn on_main_diagonal(&self, position: GridPosition) -> bool {
+        self.is_square() && position.row() == position.col()
     }
 
-    fn columns(&self) -> Vec<Vec<BoardCell>> {
-        self.rows
-            .first()
-            .unwrap()
-            .iter()
-            .enumerate()
-            .map(|(col_index, _first_cell)| {
-                (0..5)
-                    .map(|row_index| *self.cell_at_position(row_index, col_index))
-                    .collect::<Vec<BoardCell>>()
-            })
-            .collect::<Vec<Vec<BoardCell>>>()
+    fn on_anti_diagonal(&self, position: GridPosition) -> bool {
+        self.is_square() && position.row() + position.col() + 1 == self.cells.width()
+    }
+
+    fn is_corner(&self, position: GridPosition) -> bool {
+        (position.row() == 0 || position.row() + 1 == self.cells.height())
+            && (position.col() == 0 || position.col() + 1 == self.cells.width())
+    }
+
+    /// Number of distinct corner cells, which is fewer than four on single row or column boards.
+    fn corner_count(&self) -> usize {
+        self.cells
+            .positions()
+            .filter(|position| self.is_corner(*position))
+            .count()
+    }
+
+    fn score(&self, drawn_number: u8) -> usize {
+        self.unmarked_sum * drawn_number as usize
     }
 }
 
@@ -89,109 +156,182 @@ enum MarkedOrUnmarked {
     Unmarked,
 }
 
-fn mark_drawn_number(board: &mut BingoBoard, drawn_number: u8) {
-    board.rows.iter_mut().for_each(|row| {
-        row.iter_mut().for_each(|cell| {
-            if cell.value == drawn_number {
-                cell.state = MarkedOrUnmarked::Marked;
-            }
-        })
-    })
+/// A pattern of marked cells that wins a board.
+pub(crate) trait WinRule: Debug {
+    /// Whether marking the cell at `position` completed the pattern.
+    fn is_won(&self, board: &BingoBoard, position: GridPosition) -> bool;
 }
 
-#[derive(derive_new::new)]
-struct CompletionDetector;
+/// A complete row or column, as in the puzzle.
+#[derive(Debug, derive_new::new)]
+pub(crate) struct RowOrColumnRule;
+
+impl WinRule for RowOrColumnRule {
+    fn is_won(&self, board: &BingoBoard, position: GridPosition) -> bool {
+        board.row_marks[position.row()] == board.cells.width()
+            || board.column_marks[position.col()] == board.cells.height()
+    }
+}
+
+/// Either complete diagonal. Only square boards have diagonals.
+#[derive(Debug, derive_new::new)]
+pub(crate) struct DiagonalRule;
+
+impl WinRule for DiagonalRule {
+    fn is_won(&self, board: &BingoBoard, position: GridPosition) -> bool {
+        (board.on_main_diagonal(position) && board.main_diagonal_marks == board.cells.width())
+            || (board.on_anti_diagonal(position)
+                && board.anti_diagonal_marks == board.cells.width())
+    }
+}
+
+#[derive(Debug, derive_new::new)]
+pub(crate) struct FourCornersRule;
+
+impl WinRule for FourCornersRule {
+    fn is_won(&self, board: &BingoBoard, position: GridPosition) -> bool {
+        board.is_corner(position) && board.corner_marks == board.corner_count()
+    }
+}
+
+/// Every cell on the board, also known as a blackout.
+#[derive(Debug, derive_new::new)]
+pub(crate) struct FullCardRule;
+
+impl WinRule for FullCardRule {
+    fn is_won(&self, board: &BingoBoard, _position: GridPosition) -> bool {
+        board.marked_count == board.cells.width() * board.cells.height()
+    }
+}
+
+/// Completes a board when any of its win rules are met.
+#[derive(Debug, derive_new::new)]
+struct CompletionDetector {
+    win_rules: Vec<Box<dyn WinRule>>,
+}
+
+impl Default for CompletionDetector {
+    fn default() -> Self {
+        CompletionDetector::new(vec![Box::new(RowOrColumnRule::new())])
+    }
+}
 
 impl CompletionDetector {
-    fn is_complete(&self, board: &BingoBoard) -> bool {
-        has_a_fully_marked_list(board.rows()) || has_a_fully_marked_list(&board.columns())
+    fn is_complete(&self, board: &BingoBoard, position: GridPosition) -> bool {
+        self.win_rules
+            .iter()
+            .any(|win_rule| win_rule.is_won(board, position))
     }
 }
 
-fn has_a_fully_marked_list(list: &Vec<Vec<BoardCell>>) -> bool {
-    list.iter()
-        .any(|inner| inner.iter().all(|cell| cell.is_marked()))
+#[derive(Debug, Copy, Clone, derive_new::new, derive_getters::Getters)]
+pub(crate) struct Win {
+    turn: usize,
+    drawn_number: u8,
+    score: usize,
 }
 
-#[derive(derive_new::new)]
-pub(crate) struct Day4SolutionExecutor;
+#[derive(Debug, Clone, derive_new::new, derive_getters::Getters)]
+pub(crate) struct BoardResult {
+    board_index: usize,
+    win: Option<Win>,
+}
 
-impl SolutionExecutor for Day4SolutionExecutor {
-    type Input = BingoGame;
-    type Part1Output = usize;
-    type Part2Output = usize;
+impl Display for BoardResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.win {
+            Some(win) => write!(
+                f,
+                "board {} won on turn {} drawing {} with score {}",
+                self.board_index, win.turn, win.drawn_number, win.score
+            ),
+            None => write!(f, "board {} never won", self.board_index),
+        }
+    }
+}
 
-    fn part_1(&self, mut input: Self::Input) -> Self::Part1Output {
-        let completion_detector: CompletionDetector = CompletionDetector::new();
-        let mut completed_board: Option<BingoBoard> = None;
-        let mut final_drawn_number: Option<u8> = None;
-
-        for drawn_number in input.draw_order {
-            input
-                .boards
-                .iter_mut()
-                .for_each(|board| mark_drawn_number(board, drawn_number));
-
-            for board in &input.boards {
-                if completion_detector.is_complete(board) {
-                    completed_board = Some(board.clone());
-                    final_drawn_number = Some(drawn_number);
-                    break;
-                }
-            }
-            if final_drawn_number.is_some() {
-                break;
+/// Plays every draw and returns the result of each board, ordered by the turn it won on. Boards
+/// that never win come last.
+fn play(game: BingoGame, completion_detector: &CompletionDetector) -> Vec<BoardResult> {
+    let mut boards = game.boards;
+    let mut wins: Vec<Option<Win>> = vec![None; boards.len()];
+    let mut boards_remaining = boards.len();
+
+    for (turn_index, drawn_number) in game.draw_order.into_iter().enumerate() {
+        let mut completed_boards: Vec<usize> = Vec::new();
+        for &(board_index, position) in game.number_index.occurrences(drawn_number) {
+            let board = &mut boards[board_index];
+            if wins[board_index].is_none()
+                && board.mark(position)
+                && completion_detector.is_complete(board, position)
+            {
+                completed_boards.push(board_index);
             }
         }
 
-        completed_board
-            .unwrap()
-            .unmarked_numbers()
+        completed_boards
             .into_iter()
-            .map(|value| value as usize)
-            .sum::<usize>()
-            * final_drawn_number.unwrap() as usize
-    }
-
-    fn part_2(&self, mut input: Self::Input) -> Self::Part2Output {
-        let completion_detector: CompletionDetector = CompletionDetector::new();
-        let mut last_completed_board: Option<BingoBoard> = None;
-        let mut final_drawn_number: Option<u8> = None;
-
-        for drawn_number in input.draw_order {
-            input
-                .boards
-                .iter_mut()
-                .for_each(|board| mark_drawn_number(board, drawn_number));
-
-            if input.boards.len() > 1 {
-                input.boards = input
-                    .boards
-                    .into_iter()
-                    .filter(|board| !completion_detector.is_complete(board))
-                    .collect();
-            }
+            .unique()
+            .for_each(|board_index| {
+                wins[board_index] = Some(Win::new(
+                    turn_index + 1,
+                    drawn_number,
+                    boards[board_index].score(drawn_number),
+                ));
+                boards_remaining -= 1;
+            });
+
+        if boards_remaining == 0 {
+            break;
+        }
+    }
 
-            if let Ok(Some(remaining_board)) = input
-                .boards
-                .iter()
-                .filter(|board| completion_detector.is_complete(board))
-                .at_most_one()
-            {
-                last_completed_board = Some(remaining_board.clone());
-                final_drawn_number = Some(drawn_number);
-                break;
-            }
+    wins.into_iter()
+        .enumerate()
+        .map(|(board_index, win)| BoardResult::new(board_index, win))
+        .sorted_by_key(|result| (result.win.is_none(), result.win.map(|win| win.turn)))
+        .collect()
+}
+
+#[derive(derive_new::new)]
+pub(crate) struct Day4SolutionExecutor {
+    #[new(default)]
+    completion_detector: CompletionDetector,
+}
+
+impl Day4SolutionExecutor {
+    /// A board wins as soon as any of `win_rules` is met.
+    pub(crate) fn with_win_rules(win_rules: Vec<Box<dyn WinRule>>) -> Self {
+        Day4SolutionExecutor {
+            completion_detector: CompletionDetector::new(win_rules),
         }
+    }
 
-        let unmarked_numbers_sum = last_completed_board
-            .unwrap()
-            .unmarked_numbers()
-            .into_iter()
-            .map(|value| value as usize)
-            .sum::<usize>();
+    pub(crate) fn timeline(&self, game: BingoGame) -> Vec<BoardResult> {
+        play(game, &self.completion_detector)
+    }
+}
 
-        unmarked_numbers_sum * final_drawn_number.unwrap() as usize
+impl SolutionExecutor for Day4SolutionExecutor {
+    type Input = BingoGame;
+    type Part1Output = usize;
+    type Part2Output = usize;
+
+    fn part_1(&self, input: Self::Input) -> Self::Part1Output {
+        self.timeline(input)
+            .first()
+            .and_then(|result| result.win)
+            .expect("no board won")
+            .score
+    }
+
+    fn part_2(&self, input: Self::Input) -> Self::Part2Output {
+        self.timeline(input)
+            .iter()
+            .rev()
+            .find_map(|result| result.win)
+            .expect("no board won")
+            .score
     }
 }
 
@@ -199,6 +339,8 @@ impl SolutionExecutor for Day4SolutionExecutor {
 mod tests {
     use speculoos::prelude::*;
 
+    use crate::domain::solution_executor::test_random::TestRandom;
+
     use super::*;
 
     fn test_game() -> BingoGame {
@@ -241,4 +383,170 @@ mod tests {
     fn calculates_score_of_last_winning_board() {
         assert_that(&Day4SolutionExecutor::new().part_2(test_game())).is_equal_to(1924);
     }
+
+    #[test]
+    fn records_every_board_in_winning_order() {
+        let timeline: Vec<String> = Day4SolutionExecutor::new()
+            .timeline(test_game())
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_that(&timeline.len()).is_equal_to(3);
+        assert_that(&timeline.first()).is_equal_to(Some(
+            &"board 2 won on turn 12 drawing 24 with score 4512".to_string(),
+        ));
+        assert_that(&timeline.last()).is_equal_to(Some(
+            &"board 1 won on turn 15 drawing 13 with score 1924".to_string(),
+        ));
+    }
+
+    #[test]
+    fn records_boards_that_never_win() {
+        let boards = vec![vec![vec![1, 2], vec![3, 4]], vec![vec![5, 6], vec![7, 8]]];
+        let timeline = Day4SolutionExecutor::new().timeline(BingoGame::new(vec![5, 6, 1], boards));
+
+        assert_that(
+            &timeline
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+        )
+        .is_equal_to(vec![
+            "board 1 won on turn 2 drawing 6 with score 90".to_string(),
+            "board 0 never won".to_string(),
+        ]);
+    }
+
+    /// Scores every board by checking its rows and columns against all numbers drawn so far.
+    fn naive_timeline(draw_order: &[u8], boards: &[Vec<Vec<u8>>]) -> Vec<String> {
+        let mut results: Vec<(usize, String)> = Vec::new();
+        let mut won: Vec<bool> = vec![false; boards.len()];
+        for turn in 1..=draw_order.len() {
+            let drawn = &draw_order[..turn];
+            let drawn_number = draw_order[turn - 1];
+            for (board_index, board) in boards.iter().enumerate() {
+                let row_complete = board
+                    .iter()
+                    .any(|row| row.iter().all(|v| drawn.contains(v)));
+                let column_complete = (0..board[0].len())
+                    .any(|col| board.iter().all(|row| drawn.contains(&row[col])));
+                if !won[board_index] && (row_complete || column_complete) {
+                    won[board_index] = true;
+                    let unmarked: usize = board
+                        .iter()
+                        .flatten()
+                        .filter(|v| !drawn.contains(v))
+                        .map(|v| *v as usize)
+                        .sum();
+                    results.push((
+                        board_index,
+                        format!(
+                            "board {} won on turn {} drawing {} with score {}",
+                            board_index,
+                            turn,
+                            drawn_number,
+                            unmarked * drawn_number as usize
+                        ),
+                    ));
+                }
+            }
+        }
+        (0..boards.len())
+            .filter(|board_index| !won[*board_index])
+            .for_each(|board_index| {
+                results.push((board_index, format!("board {} never won", board_index)))
+            });
+        results.into_iter().map(|(_index, result)| result).collect()
+    }
+
+    #[test]
+    fn indexed_timeline_matches_naive_scoring_on_many_boards() {
+        let mut random = TestRandom::new(42);
+        let mut next_random = |bound: u64| random.below(bound) as u8;
+        let boards: Vec<Vec<Vec<u8>>> = (0..300)
+            .map(|_board| {
+                (0..5)
+                    .map(|_row| (0..5).map(|_col| next_random(100)).collect())
+                    .collect()
+            })
+            .collect();
+        let draw_order: Vec<u8> = (0..60).map(|_draw| next_random(100)).collect();
+
+        let timeline: Vec<String> = Day4SolutionExecutor::new()
+            .timeline(BingoGame::new(draw_order.clone(), boards.clone()))
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_that(&timeline).is_equal_to(naive_timeline(&draw_order, &boards));
+    }
+
+    fn three_by_three_game(draw_order: Vec<u8>) -> BingoGame {
+        BingoGame::new(
+            draw_order,
+            vec![vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]],
+        )
+    }
+
+    #[test]
+    fn wins_on_either_diagonal() {
+        let executor = Day4SolutionExecutor::with_win_rules(vec![Box::new(DiagonalRule::new())]);
+        assert_that(&executor.part_1(three_by_three_game(vec![1, 2, 3, 5, 9]))).is_equal_to(9 * 25);
+        assert_that(&executor.part_1(three_by_three_game(vec![3, 5, 7]))).is_equal_to(7 * 30);
+    }
+
+    #[test]
+    fn wins_on_four_corners() {
+        let executor = Day4SolutionExecutor::with_win_rules(vec![Box::new(FourCornersRule::new())]);
+        assert_that(&executor.part_1(three_by_three_game(vec![1, 3, 5, 7, 9]))).is_equal_to(9 * 20);
+    }
+
+    #[test]
+    fn wins_on_full_card() {
+        let executor = Day4SolutionExecutor::with_win_rules(vec![Box::new(FullCardRule::new())]);
+        let timeline = executor.timeline(three_by_three_game(vec![1, 2, 3, 4, 5, 6, 7, 8, 9]));
+        assert_that(
+            &timeline
+                .first()
+                .and_then(|result| result.win)
+                .map(|win| win.turn),
+        )
+        .is_equal_to(Some(9));
+    }
+
+    #[test]
+    fn wins_on_first_of_combined_rules() {
+        let executor = Day4SolutionExecutor::with_win_rules(vec![
+            Box::new(RowOrColumnRule::new()),
+            Box::new(DiagonalRule::new()),
+        ]);
+        let timeline = executor.timeline(three_by_three_game(vec![1, 5, 2, 9, 3]));
+        assert_that(
+            &timeline
+                .first()
+                .and_then(|result| result.win)
+                .map(|win| win.turn),
+        )
+        .is_equal_to(Some(4));
+    }
+
+    fn rectangular_test_game() -> BingoGame {
+        let boards: Vec<Vec<Vec<u8>>> = vec![
+            vec![vec![1, 2], vec![3, 4], vec![5, 6]],
+            vec![vec![7, 8], vec![9, 10], vec![11, 12]],
+        ];
+        BingoGame::new(vec![2, 4, 12, 6, 11], boards)
+    }
+
+    #[test]
+    fn calculates_score_of_first_winning_rectangular_board() {
+        assert_that(&Day4SolutionExecutor::new().part_1(rectangular_test_game())).is_equal_to(54);
+    }
+
+    #[test]
+    fn calculates_score_of_last_winning_rectangular_board() {
+        assert_that(&Day4SolutionExecutor::new().part_2(rectangular_test_game()))
+            .is_equal_to(34 * 11);
+    }
 }
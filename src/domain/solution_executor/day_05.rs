@@ -1,5 +1,10 @@
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
 
+use itertools::Itertools;
+
+use crate::domain::grid::Grid;
 use crate::domain::solution_executor::SolutionExecutor;
 
 #[derive(Debug, derive_new::new, Copy, Clone, Eq, PartialEq, Hash)]
@@ -8,46 +13,123 @@ pub(crate) struct Position {
     y: usize,
 }
 
-#[derive(Debug, derive_new::new)]
+#[derive(Debug, Clone, derive_new::new)]
 pub(crate) struct StraightLine {
     start: Position,
     end: Position,
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Orientation {
+    Horizontal,
+    Vertical,
+    /// Exactly 45 degrees.
+    Diagonal,
+    /// Any other slope.
+    Sloped,
+}
+
+/// How a line is turned into the grid points it covers.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Rasterisation {
+    /// Only the points lying exactly on the line.
+    LatticePoints,
+    /// The closest point in every column or row the line crosses.
+    Bresenham,
+}
+
 impl StraightLine {
-    fn points(&self) -> Vec<Position> {
-        if self.start.x == self.end.x {
-            potentially_descending_end_inclusive_range(self.start.y, self.end.y)
-                .map(|y| Position::new(self.start.x, y))
-                .collect()
-        } else if self.start.y == self.end.y {
-            potentially_descending_end_inclusive_range(self.start.x, self.end.x)
-                .map(|x| Position::new(x, self.start.y))
-                .collect()
+    fn points(&self, rasterisation: Rasterisation) -> Vec<Position> {
+        if self.is_axis_aligned() || self.is_diagonal() {
+            // every point on these lines is a lattice point, so both rasterisations agree
+            return self.lattice_points();
+        }
+        match rasterisation {
+            Rasterisation::LatticePoints => self.lattice_points(),
+            Rasterisation::Bresenham => self.bresenham_points(),
+        }
+    }
+
+    fn lattice_points(&self) -> Vec<Position> {
+        let (dx, dy) = self.extent();
+        let steps = gcd(dx.unsigned_abs(), dy.unsigned_abs()) as i64;
+        if steps == 0 {
+            return vec![self.start];
+        }
+        let (step_x, step_y) = (dx / steps, dy / steps);
+
+        (0..=steps)
+            .map(|step| self.offset_from_start(step * step_x, step * step_y))
+            .collect()
+    }
+
+    fn bresenham_points(&self) -> Vec<Position> {
+        let (dx, dy) = self.extent();
+        let (step_x, step_y) = (dx.signum(), dy.signum());
+        let (width, height) = (dx.abs(), -dy.abs());
+        let mut error = width + height;
+        let (mut x, mut y) = (0, 0);
+        let mut points = vec![self.start];
+
+        while (x, y) != (dx, dy) {
+            let doubled_error = 2 * error;
+            if doubled_error >= height {
+                error += height;
+                x += step_x;
+            }
+            if doubled_error <= width {
+                error += width;
+                y += step_y;
+            }
+            points.push(self.offset_from_start(x, y));
+        }
+        points
+    }
+
+    fn extent(&self) -> (i64, i64) {
+        (
+            self.end.x as i64 - self.start.x as i64,
+            self.end.y as i64 - self.start.y as i64,
+        )
+    }
+
+    fn offset_from_start(&self, x: i64, y: i64) -> Position {
+        Position::new(
+            (self.start.x as i64 + x) as usize,
+            (self.start.y as i64 + y) as usize,
+        )
+    }
+
+    fn orientation(&self) -> Orientation {
+        let (dx, dy) = self.extent();
+        if dy == 0 {
+            Orientation::Horizontal
+        } else if dx == 0 {
+            Orientation::Vertical
+        } else if dx.abs() == dy.abs() {
+            Orientation::Diagonal
         } else {
-            potentially_descending_end_inclusive_range(self.start.x, self.end.x)
-                .zip(potentially_descending_end_inclusive_range(
-                    self.start.y,
-                    self.end.y,
-                ))
-                .map(|(x, y)| Position::new(x, y))
-                .collect()
+            Orientation::Sloped
         }
     }
 
+    fn is_axis_aligned(&self) -> bool {
+        matches!(
+            self.orientation(),
+            Orientation::Horizontal | Orientation::Vertical
+        )
+    }
+
     fn is_diagonal(&self) -> bool {
-        !(self.start.x == self.end.x || self.start.y == self.end.y)
+        self.orientation() == Orientation::Diagonal
     }
 }
 
-fn potentially_descending_end_inclusive_range(
-    start: usize,
-    end: usize,
-) -> impl Iterator<Item = usize> {
-    if start > end {
-        (end..start + 1).rev().collect::<Vec<usize>>().into_iter()
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
     } else {
-        (start..end + 1).collect::<Vec<usize>>().into_iter()
+        gcd(b, a % b)
     }
 }
 
@@ -63,20 +145,435 @@ impl VentMap {
         }
     }
 
-    fn point_vent_counts(&self) -> Vec<usize> {
-        self.map.values().into_iter().copied().collect()
+    fn overlap_histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        self.map.values().for_each(|&count| {
+            *histogram.entry(count).or_insert(0) += 1;
+        });
+        histogram
+    }
+
+    fn most_overlapped(&self) -> Option<MostOverlappedPoint> {
+        self.map
+            .iter()
+            .min_by_key(|(point, &count)| (Reverse(count), point.y, point.x))
+            .map(|(point, &count)| MostOverlappedPoint::new(*point, count))
+    }
+
+    /// The number of lines covering every point from the origin to the furthest point covered.
+    fn overlap_field(&self) -> Grid<usize> {
+        let width = self.map.keys().map(|point| point.x + 1).max().unwrap_or(0);
+        let height = self.map.keys().map(|point| point.y + 1).max().unwrap_or(0);
+        Grid::new(
+            (0..height)
+                .map(|y| {
+                    (0..width)
+                        .map(|x| *self.map.get(&Position::new(x, y)).unwrap_or(&0))
+                        .collect()
+                })
+                .collect(),
+        )
+        .expect("overlap field rows are all the same width")
     }
 
-    fn record_line(&mut self, line: &StraightLine) {
-        line.points().into_iter().for_each(|point| {
+    fn record_line(&mut self, line: &StraightLine, rasterisation: Rasterisation) {
+        line.points(rasterisation).into_iter().for_each(|point| {
             let count = self.map.entry(point).or_insert(0);
             *count += 1;
         });
     }
 }
 
+/// The infinite line a segment lies on, identified by its primitive direction and offset.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+struct SupportingLine {
+    step_x: i64,
+    step_y: i64,
+    offset: i64,
+}
+
+impl SupportingLine {
+    /// Numbers the lattice points along the line so that neighbouring points differ by one.
+    fn parameter(&self, (x, y): (i64, i64)) -> i64 {
+        if self.step_x == 0 {
+            y
+        } else {
+            x.div_euclid(self.step_x)
+        }
+    }
+
+    /// The lattice point numbered `parameter`, found by stepping along the line from `anchor`.
+    fn point_at(&self, anchor: (i64, i64), parameter: i64) -> Position {
+        let steps = parameter - self.parameter(anchor);
+        Position::new(
+            (anchor.0 + steps * self.step_x) as usize,
+            (anchor.1 + steps * self.step_y) as usize,
+        )
+    }
+}
+
+#[derive(Debug)]
+struct LineSegment {
+    line: SupportingLine,
+    start: (i64, i64),
+    steps: i64,
+}
+
+impl LineSegment {
+    fn new(line: &StraightLine) -> Self {
+        let (dx, dy) = line.extent();
+        let steps = gcd(dx.unsigned_abs(), dy.unsigned_abs()) as i64;
+        let (mut step_x, mut step_y) = if steps == 0 {
+            (1, 0)
+        } else {
+            (dx / steps, dy / steps)
+        };
+        let mut start = (line.start.x as i64, line.start.y as i64);
+        if step_x < 0 || (step_x == 0 && step_y < 0) {
+            start = (line.end.x as i64, line.end.y as i64);
+            step_x = -step_x;
+            step_y = -step_y;
+        }
+        let offset = step_y * start.0 - step_x * start.1;
+
+        LineSegment {
+            line: SupportingLine {
+                step_x,
+                step_y,
+                offset,
+            },
+            start,
+            steps,
+        }
+    }
+
+    fn parameter_range(&self) -> (i64, i64) {
+        let from = self.line.parameter(self.start);
+        (from, from + self.steps)
+    }
+
+    /// The lattice point where two segments on different lines cross, if there is one.
+    fn crossing(&self, other: &LineSegment) -> Option<(i64, i64)> {
+        let cross = self.line.step_x * other.line.step_y - self.line.step_y * other.line.step_x;
+        if cross == 0 {
+            return None;
+        }
+        let (gap_x, gap_y) = (other.start.0 - self.start.0, other.start.1 - self.start.1);
+        let self_numerator = gap_x * other.line.step_y - gap_y * other.line.step_x;
+        let other_numerator = gap_x * self.line.step_y - gap_y * self.line.step_x;
+        if self_numerator % cross != 0 || other_numerator % cross != 0 {
+            return None;
+        }
+        let (self_step, other_step) = (self_numerator / cross, other_numerator / cross);
+        if !(0..=self.steps).contains(&self_step) || !(0..=other.steps).contains(&other_step) {
+            return None;
+        }
+        Some((
+            self.start.0 + self_step * self.line.step_x,
+            self.start.1 + self_step * self.line.step_y,
+        ))
+    }
+}
+
+/// A run of consecutive lattice points on a line covered by the same number of segments.
+#[derive(Debug)]
+struct CoveragePiece {
+    from: i64,
+    to: i64,
+    coverage: usize,
+}
+
+/// Counts overlaps from segment crossings and collinear overlaps without visiting every point.
+#[derive(Debug)]
+struct AnalyticVentMap {
+    coverage: HashMap<SupportingLine, Vec<CoveragePiece>>,
+    crossings: HashMap<(i64, i64), HashSet<SupportingLine>>,
+    /// A point on each line, from which the points of its coverage pieces are found.
+    anchors: HashMap<SupportingLine, (i64, i64)>,
+}
+
+impl AnalyticVentMap {
+    fn new(lines: &[&StraightLine]) -> Self {
+        let segments: Vec<LineSegment> = lines.iter().map(|line| LineSegment::new(line)).collect();
+
+        let mut ranges: HashMap<SupportingLine, Vec<(i64, i64)>> = HashMap::new();
+        let mut anchors = HashMap::new();
+        segments.iter().for_each(|segment| {
+            ranges
+                .entry(segment.line)
+                .or_default()
+                .push(segment.parameter_range());
+            anchors.entry(segment.line).or_insert(segment.start);
+        });
+        let coverage = ranges
+            .into_iter()
+            .map(|(line, ranges)| (line, sweep(ranges)))
+            .collect();
+
+        let mut crossings: HashMap<(i64, i64), HashSet<SupportingLine>> = HashMap::new();
+        segments
+            .iter()
+            .tuple_combinations()
+            .for_each(|(a, b): (&LineSegment, &LineSegment)| {
+                if let Some(point) = a.crossing(b) {
+                    let lines = crossings.entry(point).or_default();
+                    lines.insert(a.line);
+                    lines.insert(b.line);
+                }
+            });
+
+        AnalyticVentMap {
+            coverage,
+            crossings,
+            anchors,
+        }
+    }
+
+    fn coverage_at(&self, line: &SupportingLine, point: (i64, i64)) -> usize {
+        let pieces = &self.coverage[line];
+        let parameter = line.parameter(point);
+        let index = pieces.partition_point(|piece| piece.to < parameter);
+        pieces
+            .get(index)
+            .filter(|piece| piece.from <= parameter)
+            .map(|piece| piece.coverage)
+            .unwrap_or(0)
+    }
+
+    fn crossing_coverage(&self, point: (i64, i64), lines: &HashSet<SupportingLine>) -> usize {
+        lines.iter().map(|line| self.coverage_at(line, point)).sum()
+    }
+
+    /// Only piece ends and crossings can be the earliest most overlapped point.
+    fn most_overlapped(&self) -> Option<MostOverlappedPoint> {
+        let piece_ends = self.coverage.iter().flat_map(|(line, pieces)| {
+            let anchor = self.anchors[line];
+            pieces.iter().flat_map(move |piece| {
+                [piece.from, piece.to].map(|parameter| {
+                    MostOverlappedPoint::new(line.point_at(anchor, parameter), piece.coverage)
+                })
+            })
+        });
+        let crossings = self.crossings.iter().map(|(point, lines)| {
+            MostOverlappedPoint::new(
+                Position::new(point.0 as usize, point.1 as usize),
+                self.crossing_coverage(*point, lines),
+            )
+        });
+
+        piece_ends.chain(crossings).min_by_key(|most_overlapped| {
+            (
+                Reverse(most_overlapped.count),
+                most_overlapped.position.y,
+                most_overlapped.position.x,
+            )
+        })
+    }
+
+    fn overlap_histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram: BTreeMap<usize, i64> = BTreeMap::new();
+        self.coverage.values().flatten().for_each(|piece| {
+            *histogram.entry(piece.coverage).or_insert(0) += piece.to - piece.from + 1;
+        });
+
+        // each crossing point was counted once for every line through it, so move it to its total
+        self.crossings.iter().for_each(|(point, lines)| {
+            let line_coverage: Vec<usize> = lines
+                .iter()
+                .map(|line| self.coverage_at(line, *point))
+                .collect();
+            line_coverage.iter().for_each(|coverage| {
+                *histogram.entry(*coverage).or_insert(0) -= 1;
+            });
+            *histogram.entry(line_coverage.iter().sum()).or_insert(0) += 1;
+        });
+
+        histogram
+            .into_iter()
+            .filter(|(_coverage, count)| *count > 0)
+            .map(|(coverage, count)| (coverage, count as usize))
+            .collect()
+    }
+}
+
+/// Splits the covered parameter ranges of one line into runs of equal coverage.
+fn sweep(ranges: Vec<(i64, i64)>) -> Vec<CoveragePiece> {
+    let events: Vec<(i64, i64)> = ranges
+        .into_iter()
+        .flat_map(|(from, to)| [(from, 1), (to + 1, -1)])
+        .sorted()
+        .collect();
+
+    let mut pieces = Vec::new();
+    let mut coverage: i64 = 0;
+    events
+        .iter()
+        .group_by(|(parameter, _change)| *parameter)
+        .into_iter()
+        .map(|(parameter, changes)| (parameter, changes.map(|(_p, change)| change).sum::<i64>()))
+        .tuple_windows()
+        .for_each(|((from, change), (next, _next_change))| {
+            coverage += change;
+            if coverage > 0 {
+                pieces.push(CoveragePiece {
+                    from,
+                    to: next - 1,
+                    coverage: coverage as usize,
+                });
+            }
+        });
+    pieces
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum OverlapEngine {
+    /// Records every point of every line in a map.
+    Rasterised,
+    /// Works out overlaps from segment intersections, covering only lattice points.
+    Analytic,
+}
+
+#[derive(Debug, Copy, Clone, derive_new::new, derive_getters::Getters)]
+pub(crate) struct VentMapParameters {
+    rasterisation: Rasterisation,
+    engine: OverlapEngine,
+    /// A point counts as overlapping once at least this many lines cover it.
+    overlap_threshold: usize,
+}
+
+impl Default for VentMapParameters {
+    fn default() -> Self {
+        VentMapParameters::new(Rasterisation::LatticePoints, OverlapEngine::Rasterised, 2)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, derive_new::new)]
+pub(crate) struct MostOverlappedPoint {
+    position: Position,
+    count: usize,
+}
+
+/// How many points are covered by each number of lines, and which point is covered by the most.
+#[derive(Debug, Eq, PartialEq, derive_new::new)]
+pub(crate) struct OverlapReport {
+    histogram: BTreeMap<usize, usize>,
+    /// Ties go to the point that comes first reading top to bottom, left to right.
+    most_overlapped: Option<MostOverlappedPoint>,
+}
+
+impl Display for OverlapReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "lines\tpoints")?;
+        self.histogram
+            .iter()
+            .try_for_each(|(lines, points)| writeln!(f, "{}\t{}", lines, points))?;
+        match &self.most_overlapped {
+            Some(most_overlapped) => write!(
+                f,
+                "most overlapped point: {},{} covered by {} lines",
+                most_overlapped.position.x, most_overlapped.position.y, most_overlapped.count
+            ),
+            None => write!(f, "no points covered"),
+        }
+    }
+}
+
+/// The largest number of cells an overlap field may have, 4096 by 4096.
+const MAX_OVERLAP_FIELD_CELLS: usize = 1 << 24;
+
+#[derive(Debug, thiserror::Error, derive_new::new, Eq, PartialEq)]
+#[error("the overlap field would be {width} by {height} points, more than the {limit} allowed")]
+pub(crate) struct OverlapFieldTooLargeError {
+    width: usize,
+    height: usize,
+    limit: usize,
+}
+
 #[derive(derive_new::new)]
-pub(crate) struct Day5SolutionExecutor;
+pub(crate) struct Day5SolutionExecutor {
+    #[new(default)]
+    parameters: VentMapParameters,
+}
+
+impl Day5SolutionExecutor {
+    pub(crate) fn with_parameters(parameters: VentMapParameters) -> Self {
+        Day5SolutionExecutor { parameters }
+    }
+
+    /// The number of lines covering each point, with rows running along y. Always rasterises the
+    /// lines, whichever overlap engine is selected, so refuses fields of more than
+    /// `MAX_OVERLAP_FIELD_CELLS` points before drawing any line.
+    pub(crate) fn overlap_field(
+        &self,
+        input: Vec<StraightLine>,
+        axis_aligned_only: bool,
+    ) -> Result<Grid<usize>, OverlapFieldTooLargeError> {
+        let lines: Vec<&StraightLine> = input
+            .iter()
+            .filter(|line| !axis_aligned_only || line.is_axis_aligned())
+            .collect();
+        let endpoints = || lines.iter().flat_map(|line| [line.start, line.end]);
+        let width = endpoints().map(|point| point.x + 1).max().unwrap_or(0);
+        let height = endpoints().map(|point| point.y + 1).max().unwrap_or(0);
+        if width
+            .checked_mul(height)
+            .is_none_or(|cells| cells > MAX_OVERLAP_FIELD_CELLS)
+        {
+            return Err(OverlapFieldTooLargeError::new(
+                width,
+                height,
+                MAX_OVERLAP_FIELD_CELLS,
+            ));
+        }
+
+        Ok(self.rasterise(&lines).overlap_field())
+    }
+
+    /// Reports how the points are covered, by every line or only the horizontal and vertical ones.
+    pub(crate) fn overlap_report(
+        &self,
+        input: Vec<StraightLine>,
+        axis_aligned_only: bool,
+    ) -> OverlapReport {
+        let lines: Vec<&StraightLine> = input
+            .iter()
+            .filter(|line| !axis_aligned_only || line.is_axis_aligned())
+            .collect();
+        match self.parameters.engine {
+            OverlapEngine::Rasterised => {
+                let vent_map = self.rasterise(&lines);
+                OverlapReport::new(vent_map.overlap_histogram(), vent_map.most_overlapped())
+            }
+            OverlapEngine::Analytic => {
+                let vent_map = AnalyticVentMap::new(&lines);
+                OverlapReport::new(vent_map.overlap_histogram(), vent_map.most_overlapped())
+            }
+        }
+    }
+
+    fn rasterise(&self, lines: &[&StraightLine]) -> VentMap {
+        let mut vent_map = VentMap::new();
+        lines
+            .iter()
+            .for_each(|line| vent_map.record_line(line, self.parameters.rasterisation));
+        vent_map
+    }
+
+    /// How many points are covered by each number of lines.
+    fn overlap_histogram(&self, lines: &[&StraightLine]) -> BTreeMap<usize, usize> {
+        match self.parameters.engine {
+            OverlapEngine::Rasterised => self.rasterise(lines).overlap_histogram(),
+            OverlapEngine::Analytic => AnalyticVentMap::new(lines).overlap_histogram(),
+        }
+    }
+
+    fn count_overlapping_points(&self, histogram: BTreeMap<usize, usize>) -> usize {
+        histogram
+            .range(self.parameters.overlap_threshold..)
+            .map(|(_coverage, count)| count)
+            .sum()
+    }
+}
 
 impl SolutionExecutor for Day5SolutionExecutor {
     type Input = Vec<StraightLine>;
@@ -84,26 +581,14 @@ impl SolutionExecutor for Day5SolutionExecutor {
     type Part2Output = usize;
 
     fn part_1(&self, input: Self::Input) -> Self::Part1Output {
-        let mut vent_map = VentMap::new();
-        input
-            .iter()
-            .filter(|line| !line.is_diagonal())
-            .for_each(|line| vent_map.record_line(line));
-        vent_map
-            .point_vent_counts()
-            .into_iter()
-            .filter(|&count| count >= 2)
-            .count()
+        let lines: Vec<&StraightLine> =
+            input.iter().filter(|line| line.is_axis_aligned()).collect();
+        self.count_overlapping_points(self.overlap_histogram(&lines))
     }
 
     fn part_2(&self, input: Self::Input) -> Self::Part2Output {
-        let mut vent_map = VentMap::new();
-        input.iter().for_each(|line| vent_map.record_line(line));
-        vent_map
-            .point_vent_counts()
-            .into_iter()
-            .filter(|&count| count >= 2)
-            .count()
+        let lines: Vec<&StraightLine> = input.iter().collect();
+        self.count_overlapping_points(self.overlap_histogram(&lines))
     }
 }
 
@@ -111,6 +596,8 @@ impl SolutionExecutor for Day5SolutionExecutor {
 mod tests {
     use speculoos::prelude::*;
 
+    use crate::domain::solution_executor::test_random::TestRandom;
+
     use super::*;
 
     fn test_data() -> Vec<StraightLine> {
@@ -137,4 +624,157 @@ mod tests {
     fn counts_points_where_at_least_two_lines_intersect() {
         assert_that(&Day5SolutionExecutor::new().part_2(test_data())).is_equal_to(12)
     }
+
+    #[test]
+    fn counts_points_where_at_least_two_lines_intersect_with_bresenham_rasterisation() {
+        let executor = Day5SolutionExecutor::with_parameters(VentMapParameters::new(
+            Rasterisation::Bresenham,
+            OverlapEngine::Rasterised,
+            2,
+        ));
+        assert_that(&executor.part_2(test_data())).is_equal_to(12)
+    }
+
+    fn analytic_executor() -> Day5SolutionExecutor {
+        Day5SolutionExecutor::with_parameters(VentMapParameters::new(
+            Rasterisation::LatticePoints,
+            OverlapEngine::Analytic,
+            2,
+        ))
+    }
+
+    #[test]
+    fn counts_points_where_at_least_two_lines_intersect_analytically() {
+        assert_that(&analytic_executor().part_1(test_data())).is_equal_to(5);
+        assert_that(&analytic_executor().part_2(test_data())).is_equal_to(12);
+    }
+
+    #[test]
+    fn counts_overlaps_of_huge_lines_analytically() {
+        let lines = vec![
+            StraightLine::new(Position::new(0, 0), Position::new(4_000_000, 0)),
+            StraightLine::new(Position::new(1_000_000, 0), Position::new(9_000_000, 0)),
+            StraightLine::new(Position::new(2_000_000, 5), Position::new(2_000_000, 0)),
+        ];
+        assert_that(&analytic_executor().part_1(lines)).is_equal_to(3_000_001);
+    }
+
+    #[test]
+    fn analytic_histogram_matches_rasterised_histogram_on_random_lines() {
+        let mut random = TestRandom::new(7);
+        let mut next_random = |bound: u64| random.below(bound) as usize;
+
+        (0..20).for_each(|_round| {
+            let input: Vec<StraightLine> = (0..40)
+                .map(|_line| {
+                    let start = Position::new(next_random(30), next_random(30));
+                    let end = match next_random(4) {
+                        0 => Position::new(start.x, next_random(30)),
+                        1 => Position::new(next_random(30), start.y),
+                        2 => {
+                            let length = next_random(15);
+                            Position::new(start.x + length, start.y + length)
+                        }
+                        _ => Position::new(next_random(30), next_random(30)),
+                    };
+                    StraightLine::new(start, end)
+                })
+                .collect();
+
+            assert_that(&analytic_executor().overlap_report(input.clone(), false))
+                .is_equal_to(Day5SolutionExecutor::new().overlap_report(input, false));
+        });
+    }
+
+    #[test]
+    fn counts_points_covered_by_at_least_threshold_lines() {
+        let executor = Day5SolutionExecutor::with_parameters(VentMapParameters::new(
+            Rasterisation::LatticePoints,
+            OverlapEngine::Rasterised,
+            3,
+        ));
+        assert_that(&executor.part_2(test_data())).is_equal_to(2)
+    }
+
+    #[test]
+    fn reports_overlap_histogram_and_most_overlapped_point() {
+        let report = Day5SolutionExecutor::new().overlap_report(test_data(), false);
+        assert_that(&report.to_string()).is_equal_to(
+            "lines\tpoints\n1\t27\n2\t10\n3\t2\nmost overlapped point: 4,4 covered by 3 lines"
+                .to_string(),
+        );
+    }
+
+    #[test]
+    fn maps_number_of_lines_covering_each_point() {
+        let input = vec![
+            StraightLine::new(Position::new(0, 0), Position::new(2, 0)),
+            StraightLine::new(Position::new(1, 0), Position::new(1, 1)),
+            StraightLine::new(Position::new(0, 1), Position::new(1, 0)),
+        ];
+        assert_that(
+            &Day5SolutionExecutor::new()
+                .overlap_field(input, false)
+                .unwrap()
+                .to_string(),
+        )
+        .is_equal_to("131\n110".to_string());
+    }
+
+    #[test]
+    fn maps_only_axis_aligned_lines() {
+        let input = vec![
+            StraightLine::new(Position::new(0, 0), Position::new(2, 0)),
+            StraightLine::new(Position::new(0, 2), Position::new(2, 0)),
+        ];
+        assert_that(
+            &Day5SolutionExecutor::new()
+                .overlap_field(input, true)
+                .unwrap()
+                .to_string(),
+        )
+        .is_equal_to("111".to_string());
+    }
+
+    #[test]
+    fn refuses_overlap_field_too_large_to_draw() {
+        let input = vec![StraightLine::new(
+            Position::new(0, 0),
+            Position::new(4_000_000, 4_000_000),
+        )];
+        assert_that(&Day5SolutionExecutor::new().overlap_field(input, false)).is_equal_to(Err(
+            OverlapFieldTooLargeError::new(4_000_001, 4_000_001, MAX_OVERLAP_FIELD_CELLS),
+        ));
+    }
+
+    #[test]
+    fn distinguishes_diagonal_lines_from_other_slopes() {
+        let diagonal = StraightLine::new(Position::new(8, 0), Position::new(0, 8));
+        let sloped = StraightLine::new(Position::new(0, 0), Position::new(4, 2));
+        assert_that(&diagonal.orientation()).is_equal_to(Orientation::Diagonal);
+        assert_that(&sloped.orientation()).is_equal_to(Orientation::Sloped);
+        assert_that(&sloped.is_diagonal()).is_false();
+    }
+
+    #[test]
+    fn rasterises_sloped_line_to_lattice_points() {
+        let line = StraightLine::new(Position::new(6, 3), Position::new(0, 0));
+        assert_that(&line.points(Rasterisation::LatticePoints)).is_equal_to(vec![
+            Position::new(6, 3),
+            Position::new(4, 2),
+            Position::new(2, 1),
+            Position::new(0, 0),
+        ]);
+    }
+
+    #[test]
+    fn rasterises_sloped_line_with_bresenham() {
+        let line = StraightLine::new(Position::new(0, 0), Position::new(3, 1));
+        assert_that(&line.points(Rasterisation::Bresenham)).is_equal_to(vec![
+            Position::new(0, 0),
+            Position::new(1, 0),
+            Position::new(2, 1),
+            Position::new(3, 1),
+        ]);
+    }
 }
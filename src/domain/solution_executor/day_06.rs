@@ -1,60 +1,240 @@
 use crate::domain::solution_executor::SolutionExecutor;
-use std::collections::HashMap;
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum GrowthEngine {
+    /// Ages the school one day at a time.
+    DayByDay,
+    /// Raises the one day transition matrix to the power of the day count, so the number of
+    /// steps grows with the number of digits in the day count.
+    MatrixPower,
+}
+
+#[derive(Debug, Copy, Clone, derive_new::new, derive_getters::Getters)]
+pub(crate) struct LanternfishParameters {
+    /// The timer a fish restarts from after giving birth.
+    reset_timer: u8,
+    /// The timer a newborn fish starts from.
+    newborn_timer: u8,
+    /// Number of days simulated for part 1.
+    part_1_days: usize,
+    /// Number of days simulated for part 2.
+    part_2_days: usize,
+    engine: GrowthEngine,
+}
+
+impl Default for LanternfishParameters {
+    fn default() -> Self {
+        LanternfishParameters::new(6, 8, 80, 256, GrowthEngine::DayByDay)
+    }
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new, Eq, PartialEq)]
+#[error("the lanternfish population after {days} days is too large to count")]
+pub(crate) struct PopulationOverflowError {
+    days: usize,
+}
+
+/// The number of fish with each timer value.
+#[derive(Debug, Clone, Eq, PartialEq)]
 struct DayState {
-    timers: HashMap<u8, u64>,
+    timers: Vec<u128>,
+    reset_timer: usize,
+    newborn_timer: usize,
 }
 
 impl DayState {
-    fn new(timers: Vec<u8>) -> Self {
-        let mut map = HashMap::new();
-        timers.into_iter().for_each(|timer| {
-            let count = map.entry(timer).or_insert(0);
-            *count += 1;
-        });
-        DayState { timers: map }
+    fn new(timers: Vec<u8>, parameters: &LanternfishParameters) -> Self {
+        let largest_timer = timers
+            .iter()
+            .chain([&parameters.reset_timer, &parameters.newborn_timer])
+            .max()
+            .copied()
+            .unwrap_or(0) as usize;
+        let mut counts = vec![0; largest_timer + 1];
+        timers
+            .into_iter()
+            .for_each(|timer| counts[timer as usize] += 1);
+        DayState {
+            timers: counts,
+            reset_timer: parameters.reset_timer as usize,
+            newborn_timer: parameters.newborn_timer as usize,
+        }
     }
 
-    fn tick_day(&mut self) {
-        let old_map = self.timers.clone();
-        self.timers.insert(8, *old_map.get(&0).unwrap_or(&0));
-        self.timers.insert(7, *old_map.get(&8).unwrap_or(&0));
-        self.timers.insert(
-            6,
-            *old_map.get(&7).unwrap_or(&0) + *old_map.get(&0).unwrap_or(&0),
-        );
-        self.timers.insert(5, *old_map.get(&6).unwrap_or(&0));
-        self.timers.insert(4, *old_map.get(&5).unwrap_or(&0));
-        self.timers.insert(3, *old_map.get(&4).unwrap_or(&0));
-        self.timers.insert(2, *old_map.get(&3).unwrap_or(&0));
-        self.timers.insert(1, *old_map.get(&2).unwrap_or(&0));
-        self.timers.insert(0, *old_map.get(&1).unwrap_or(&0));
+    /// Returns `None` if the number of fish with some timer value no longer fits.
+    fn tick_day(&mut self) -> Option<()> {
+        let spawning = self.timers[0];
+        self.timers.rotate_left(1);
+        let last_timer = self.timers.len() - 1;
+        self.timers[last_timer] = 0;
+        self.timers[self.newborn_timer] = self.timers[self.newborn_timer].checked_add(spawning)?;
+        self.timers[self.reset_timer] = self.timers[self.reset_timer].checked_add(spawning)?;
+        Some(())
+    }
+
+    fn fish_count(&self) -> Option<u128> {
+        self.timers
+            .iter()
+            .try_fold(0u128, |total, &count| total.checked_add(count))
+    }
+}
+
+/// How many fish with each timer value a single fish turns into, with rows for the timer values
+/// after the transition and columns for the timer value before it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct TransitionMatrix {
+    entries: Vec<Vec<u128>>,
+}
+
+impl TransitionMatrix {
+    fn identity(size: usize) -> Self {
+        TransitionMatrix {
+            entries: (0..size)
+                .map(|row| (0..size).map(|col| (row == col) as u128).collect())
+                .collect(),
+        }
     }
 
-    fn fish_count(&self) -> u64 {
-        self.timers.values().sum()
+    fn one_day(state: &DayState) -> Self {
+        let size = state.timers.len();
+        let mut entries = vec![vec![0; size]; size];
+        (1..size).for_each(|timer| entries[timer - 1][timer] = 1);
+        entries[state.reset_timer][0] += 1;
+        entries[state.newborn_timer][0] += 1;
+        TransitionMatrix { entries }
+    }
+
+    fn size(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn multiply(&self, other: &TransitionMatrix) -> Option<TransitionMatrix> {
+        let size = self.size();
+        let entries = (0..size)
+            .map(|row| {
+                (0..size)
+                    .map(|col| {
+                        (0..size).try_fold(0u128, |total, k| {
+                            total.checked_add(
+                                self.entries[row][k].checked_mul(other.entries[k][col])?,
+                            )
+                        })
+                    })
+                    .collect::<Option<Vec<u128>>>()
+            })
+            .collect::<Option<Vec<Vec<u128>>>>()?;
+        Some(TransitionMatrix { entries })
+    }
+
+    fn power(&self, mut exponent: usize) -> Option<TransitionMatrix> {
+        let mut result = TransitionMatrix::identity(self.size());
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.multiply(&base)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = base.multiply(&base)?;
+            }
+        }
+        Some(result)
+    }
+
+    fn apply(&self, state: &DayState) -> Option<DayState> {
+        let timers = self
+            .entries
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(state.timers.iter())
+                    .try_fold(0u128, |total, (&entry, &count)| {
+                        total.checked_add(entry.checked_mul(count)?)
+                    })
+            })
+            .collect::<Option<Vec<u128>>>()?;
+        Some(DayState {
+            timers,
+            ..state.clone()
+        })
     }
 }
 
 #[derive(derive_new::new)]
-pub(crate) struct Day6SolutionExecutor;
+pub(crate) struct Day6SolutionExecutor {
+    #[new(default)]
+    parameters: LanternfishParameters,
+}
+
+impl Day6SolutionExecutor {
+    pub(crate) fn with_parameters(parameters: LanternfishParameters) -> Self {
+        Day6SolutionExecutor { parameters }
+    }
+
+    fn population_after(
+        &self,
+        input: Vec<u8>,
+        days: usize,
+    ) -> Result<u128, PopulationOverflowError> {
+        let state = DayState::new(input, &self.parameters);
+        let population = match self.parameters.engine {
+            GrowthEngine::DayByDay => Self::grow_day_by_day(state, days),
+            GrowthEngine::MatrixPower => Self::grow_by_matrix_power(state, days),
+        };
+        population.ok_or_else(|| PopulationOverflowError::new(days))
+    }
+
+    /// The population at the start and after each of the first `days` days, always grown day by
+    /// day whichever engine is selected.
+    pub(crate) fn population_series(
+        &self,
+        input: Vec<u8>,
+        days: usize,
+    ) -> Result<Vec<u128>, PopulationOverflowError> {
+        let mut state = DayState::new(input, &self.parameters);
+        let mut series = Vec::with_capacity(days + 1);
+        (0..=days)
+            .try_for_each(|day| {
+                if day > 0 {
+                    state.tick_day()?;
+                }
+                series.push(state.fish_count()?);
+                Some(())
+            })
+            .ok_or_else(|| PopulationOverflowError::new(days))?;
+        Ok(series)
+    }
+
+    fn grow_day_by_day(mut state: DayState, days: usize) -> Option<u128> {
+        if state.fish_count()? == 0 {
+            return Some(0);
+        }
+        (0..days).try_for_each(|_day| state.tick_day())?;
+        state.fish_count()
+    }
+
+    fn grow_by_matrix_power(state: DayState, days: usize) -> Option<u128> {
+        TransitionMatrix::one_day(&state)
+            .power(days)
+            .and_then(|transition| transition.apply(&state))
+            .and_then(|state| state.fish_count())
+            // the intermediate powers can overflow when the final count does not, so make sure
+            // day by day, which stops on the first day that overflows
+            .or_else(|| Self::grow_day_by_day(state, days))
+    }
+}
 
 impl SolutionExecutor for Day6SolutionExecutor {
     type Input = Vec<u8>;
-    type Part1Output = u64;
-    type Part2Output = u64;
+    type Part1Output = Result<u128, PopulationOverflowError>;
+    type Part2Output = Result<u128, PopulationOverflowError>;
 
     fn part_1(&self, input: Self::Input) -> Self::Part1Output {
-        let mut state = DayState::new(input);
-        (0..80).for_each(|_day| state.tick_day());
-        state.fish_count()
+        self.population_after(input, self.parameters.part_1_days)
     }
 
     fn part_2(&self, input: Self::Input) -> Self::Part2Output {
-        let mut state = DayState::new(input);
-        (0..256).for_each(|_day| state.tick_day());
-        state.fish_count()
+        self.population_after(input, self.parameters.part_2_days)
     }
 }
 
@@ -70,11 +250,89 @@ mod tests {
 
     #[test]
     fn counts_number_of_fish_after_80_days() {
-        assert_that(&Day6SolutionExecutor::new().part_1(test_data())).is_equal_to(5934);
+        assert_that(&Day6SolutionExecutor::new().part_1(test_data())).is_equal_to(Ok(5934));
     }
 
     #[test]
     fn counts_number_of_fish_after_256_days() {
-        assert_that(&Day6SolutionExecutor::new().part_2(test_data())).is_equal_to(26984457539);
+        assert_that(&Day6SolutionExecutor::new().part_2(test_data())).is_equal_to(Ok(26984457539));
+    }
+
+    fn matrix_power_executor() -> Day6SolutionExecutor {
+        Day6SolutionExecutor::with_parameters(LanternfishParameters::new(
+            6,
+            8,
+            80,
+            256,
+            GrowthEngine::MatrixPower,
+        ))
+    }
+
+    #[test]
+    fn counts_number_of_fish_by_matrix_power() {
+        let executor = matrix_power_executor();
+        assert_that(&executor.part_1(test_data())).is_equal_to(Ok(5934));
+        assert_that(&executor.part_2(test_data())).is_equal_to(Ok(26984457539));
+    }
+
+    #[test]
+    fn matrix_power_matches_day_by_day_up_to_overflow() {
+        let day_by_day = Day6SolutionExecutor::new();
+        let matrix_power = matrix_power_executor();
+        let last_countable_day = (0..)
+            .take_while(|&days| day_by_day.population_after(test_data(), days).is_ok())
+            .last()
+            .unwrap();
+
+        (0..=last_countable_day + 1).for_each(|days| {
+            assert_that(&matrix_power.population_after(test_data(), days))
+                .is_equal_to(day_by_day.population_after(test_data(), days));
+        });
+        assert_that(&matrix_power.population_after(test_data(), last_countable_day + 1))
+            .is_equal_to(Err(PopulationOverflowError::new(last_countable_day + 1)));
+    }
+
+    #[test]
+    fn reports_overflow_for_huge_day_counts() {
+        let executor = matrix_power_executor();
+        assert_that(&executor.population_after(test_data(), 1_000_000))
+            .is_equal_to(Err(PopulationOverflowError::new(1_000_000)));
+    }
+
+    #[test]
+    fn counts_no_fish_for_empty_school() {
+        let executor = matrix_power_executor();
+        assert_that(&executor.population_after(Vec::new(), 1_000_000)).is_equal_to(Ok(0));
+    }
+
+    #[test]
+    fn records_population_for_each_day() {
+        assert_that(&Day6SolutionExecutor::new().population_series(test_data(), 18)).is_equal_to(
+            Ok(vec![
+                5, 5, 6, 7, 9, 10, 10, 10, 10, 11, 12, 15, 17, 19, 20, 20, 21, 22, 26,
+            ]),
+        );
+    }
+
+    #[test]
+    fn counts_fish_with_custom_timers() {
+        let day_by_day = Day6SolutionExecutor::with_parameters(LanternfishParameters::new(
+            1,
+            2,
+            5,
+            5,
+            GrowthEngine::DayByDay,
+        ));
+        let matrix_power = Day6SolutionExecutor::with_parameters(LanternfishParameters::new(
+            1,
+            2,
+            5,
+            5,
+            GrowthEngine::MatrixPower,
+        ));
+
+        assert_that(&day_by_day.population_series(vec![0], 5))
+            .is_equal_to(Ok(vec![1, 2, 2, 3, 4, 5]));
+        assert_that(&matrix_power.part_1(vec![0])).is_equal_to(Ok(5));
     }
 }
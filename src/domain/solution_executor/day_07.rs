@@ -1,71 +1,460 @@
+use std::fmt::{Display, Formatter};
+
 use rayon::prelude::*;
 
 use crate::domain::solution_executor::SolutionExecutor;
 
 trait FuelCalculator {
-    fn calculate(&self, current_position: &u64, target_position: &u64) -> u64;
+    /// `None` if the fuel is too much to count.
+    fn calculate(&self, current_position: &u64, target_position: &u64) -> Option<u64>;
+
+    /// A position at or next to the cheapest one, from which the search starts. Finds the bottom
+    /// of the total fuel curve by binary search, which is only right if the curve is convex.
+    fn estimate_optimum(&self, starting_positions: &[u64]) -> u64 {
+        let total_fuel = |target_position: u64| {
+            fuel_rank(
+                starting_positions
+                    .iter()
+                    .try_fold(0u64, |total, starting_position| {
+                        total.checked_add(self.calculate(starting_position, &target_position)?)
+                    }),
+            )
+        };
+        let mut lower_bound_position = *starting_positions.iter().min().unwrap();
+        let mut upper_bound_position = *starting_positions.iter().max().unwrap();
+
+        while lower_bound_position < upper_bound_position {
+            let middle = lower_bound_position + (upper_bound_position - lower_bound_position) / 2;
+            if total_fuel(middle + 1) >= total_fuel(middle) {
+                upper_bound_position = middle;
+            } else {
+                lower_bound_position = middle + 1;
+            }
+        }
+        lower_bound_position
+    }
+
+    /// The first and last of the cheapest positions, if they are known without trying positions
+    /// around the one the search finds.
+    fn cheapest_range(&self, _starting_positions: &[u64]) -> Option<(u64, u64)> {
+        None
+    }
+
+    /// Whether moving a crab up to `max_distance` steps costs a convex, and so never decreasing,
+    /// amount of fuel. The total fuel curve is then convex too.
+    fn is_convex_up_to(&self, max_distance: u64) -> bool;
+}
+
+/// Orders fuel totals, with those too large to count above every other.
+fn fuel_rank(fuel: Option<u64>) -> u128 {
+    fuel.map_or(u128::MAX, u128::from)
 }
 
 #[derive(derive_new::new)]
 struct SimpleFuelCalculator;
 
 impl FuelCalculator for SimpleFuelCalculator {
-    fn calculate(&self, current_position: &u64, target_position: &u64) -> u64 {
-        current_position.max(target_position) - current_position.min(target_position)
+    fn calculate(&self, current_position: &u64, target_position: &u64) -> Option<u64> {
+        Some(current_position.max(target_position) - current_position.min(target_position))
+    }
+
+    /// The median minimises the total distance.
+    fn estimate_optimum(&self, starting_positions: &[u64]) -> u64 {
+        median_range(starting_positions).0
+    }
+
+    fn cheapest_range(&self, starting_positions: &[u64]) -> Option<(u64, u64)> {
+        Some(median_range(starting_positions))
     }
+
+    fn is_convex_up_to(&self, _max_distance: u64) -> bool {
+        true
+    }
+}
+
+/// The two middle crabs, between which every position is the same total distance from the crabs.
+/// They are the same crab if there is an odd number of crabs.
+fn median_range(starting_positions: &[u64]) -> (u64, u64) {
+    let mut sorted = starting_positions.to_vec();
+    sorted.sort_unstable();
+    (sorted[(sorted.len() - 1) / 2], sorted[sorted.len() / 2])
 }
 
 #[derive(derive_new::new)]
 struct AccurateFuelCalculator;
 
 impl FuelCalculator for AccurateFuelCalculator {
-    fn calculate(&self, current_position: &u64, target_position: &u64) -> u64 {
+    fn calculate(&self, current_position: &u64, target_position: &u64) -> Option<u64> {
         let distance =
-            current_position.max(target_position) - current_position.min(target_position);
-        (1..distance + 1).sum()
+            (current_position.max(target_position) - current_position.min(target_position)) as u128;
+        (distance * (distance + 1) / 2).try_into().ok()
+    }
+
+    /// The cheapest position is within half a step of the mean.
+    fn estimate_optimum(&self, starting_positions: &[u64]) -> u64 {
+        let sum: u128 = starting_positions
+            .iter()
+            .map(|&position| position as u128)
+            .sum();
+        (sum / starting_positions.len() as u128) as u64
+    }
+
+    fn is_convex_up_to(&self, _max_distance: u64) -> bool {
+        true
+    }
+}
+
+#[derive(derive_new::new)]
+struct PolynomialFuelCalculator {
+    /// Coefficients of increasing powers of the distance, starting from the constant term.
+    coefficients: Vec<i64>,
+}
+
+impl FuelCalculator for PolynomialFuelCalculator {
+    /// A crab never makes fuel, so negative costs count as none.
+    fn calculate(&self, current_position: &u64, target_position: &u64) -> Option<u64> {
+        let distance =
+            (current_position.max(target_position) - current_position.min(target_position)) as i128;
+        let cost = self
+            .coefficients
+            .iter()
+            .rev()
+            .fold(0i128, |cost, &coefficient| {
+                cost.saturating_mul(distance)
+                    .saturating_add(coefficient as i128)
+            });
+        cost.max(0).try_into().ok()
+    }
+
+    /// Checked at no more than `CONVEXITY_SAMPLES` distances spread across the range, which is
+    /// exact up to cubic costs.
+    fn is_convex_up_to(&self, max_distance: u64) -> bool {
+        let first_step_costs_more = match (self.exact_cost(0), self.exact_cost(1)) {
+            (Some(standing_still), Some(one_step)) => one_step >= standing_still,
+            _ => true,
+        };
+        let spacing = (max_distance / CONVEXITY_SAMPLES).max(1) as usize;
+        let last_distance = max_distance.checked_sub(1).filter(|&distance| distance > 0);
+        first_step_costs_more
+            && (1..max_distance)
+                .step_by(spacing)
+                .chain(last_distance)
+                .map_while(|distance| self.second_difference(distance))
+                .all(|second_difference| second_difference >= 0)
+    }
+
+    /// A constant cost makes every position between the outermost crabs as cheap as any other,
+    /// and a cost linear in the distance ties the same positions as the linear fuel cost.
+    fn cheapest_range(&self, starting_positions: &[u64]) -> Option<(u64, u64)> {
+        let degree = self
+            .coefficients
+            .iter()
+            .rposition(|&coefficient| coefficient != 0);
+        match (degree, self.coefficients.as_slice()) {
+            (None, _) | (Some(0), _) => Some((
+                *starting_positions.iter().min().unwrap(),
+                *starting_positions.iter().max().unwrap(),
+            )),
+            (Some(1), [constant, slope, ..]) if *constant >= 0 && *slope > 0 => {
+                Some(median_range(starting_positions))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl PolynomialFuelCalculator {
+    /// The cost of moving `distance` steps without clamping, or `None` if it is too far from zero
+    /// to compare safely.
+    fn exact_cost(&self, distance: u64) -> Option<i128> {
+        let comparable_limit = i128::MAX / 4;
+        self.coefficients
+            .iter()
+            .rev()
+            .try_fold(0i128, |cost, &coefficient| {
+                cost.checked_mul(distance as i128)?
+                    .checked_add(coefficient as i128)
+            })
+            .filter(|cost| (-comparable_limit..=comparable_limit).contains(cost))
+    }
+
+    fn second_difference(&self, distance: u64) -> Option<i128> {
+        Some(
+            self.exact_cost(distance + 1)? - 2 * self.exact_cost(distance)?
+                + self.exact_cost(distance - 1)?,
+        )
+    }
+}
+
+const CONVEXITY_SAMPLES: u64 = 10_000;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum FuelCost {
+    /// One unit of fuel per step, as in part 1.
+    Linear,
+    /// One more unit of fuel for each step than the step before, as in part 2.
+    Triangular,
+    /// A polynomial in the distance moved, with coefficients starting from the constant term.
+    Polynomial(Vec<i64>),
+}
+
+impl FuelCost {
+    fn calculator(&self) -> Box<dyn FuelCalculator + Send + Sync> {
+        match self {
+            FuelCost::Linear => Box::new(SimpleFuelCalculator::new()),
+            FuelCost::Triangular => Box::new(AccurateFuelCalculator::new()),
+            FuelCost::Polynomial(coefficients) => {
+                Box::new(PolynomialFuelCalculator::new(coefficients.clone()))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, derive_new::new, derive_getters::Getters)]
+pub(crate) struct AlignmentParameters {
+    part_1_fuel_cost: FuelCost,
+    part_2_fuel_cost: FuelCost,
+    /// Also tries every position between the outermost crabs and fails if the fast search
+    /// disagrees.
+    check_with_brute_force: bool,
+}
+
+impl Default for AlignmentParameters {
+    fn default() -> Self {
+        AlignmentParameters::new(FuelCost::Linear, FuelCost::Triangular, false)
+    }
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new, Eq, PartialEq)]
+#[error("the fuel used aligning at position {position} is too large to count")]
+pub(crate) struct FuelOverflowError {
+    position: u64,
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new, Eq, PartialEq)]
+#[error(
+    "the fast search found {} fuel but trying every position found {}",
+    describe_fuel(.searched),
+    describe_fuel(.brute_force)
+)]
+pub(crate) struct SearchDisagreementError {
+    searched: Option<u64>,
+    brute_force: Option<u64>,
+}
+
+fn describe_fuel(fuel: &Option<u64>) -> String {
+    fuel.map_or_else(|| "too much".to_string(), |fuel| fuel.to_string())
+}
+
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub(crate) enum AlignmentError {
+    #[error(transparent)]
+    FuelOverflow(#[from] FuelOverflowError),
+    #[error(transparent)]
+    SearchDisagreement(#[from] SearchDisagreementError),
+}
+
+/// The cheapest positions to align at, which always form a contiguous range.
+#[derive(Debug, Eq, PartialEq, derive_new::new, derive_getters::Getters)]
+pub(crate) struct Alignment {
+    first_position: u64,
+    last_position: u64,
+    fuel: u64,
+}
+
+impl Display for Alignment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.first_position == self.last_position {
+            write!(
+                f,
+                "align at position {} for {} fuel",
+                self.first_position, self.fuel
+            )
+        } else {
+            write!(
+                f,
+                "align at positions {} to {} for {} fuel",
+                self.first_position, self.last_position, self.fuel
+            )
+        }
     }
 }
 
 #[derive(derive_new::new)]
-struct OptimumUsageFinder<FC: FuelCalculator> {
-    fuel_calculator: FC,
+struct OptimumUsageFinder {
+    fuel_calculator: Box<dyn FuelCalculator + Send + Sync>,
+    /// Also tries every position and fails if the fast search disagrees.
+    check_with_brute_force: bool,
 }
 
-impl<FC: FuelCalculator + Send + Sync> OptimumUsageFinder<FC> {
-    fn find_lowest(&self, starting_positions: Vec<u64>) -> u64 {
+impl OptimumUsageFinder {
+    /// Every cheapest position, trying the positions on either side of the one the search finds
+    /// unless the fuel calculator already knows them.
+    fn find_optimum(&self, starting_positions: &[u64]) -> Result<Alignment, AlignmentError> {
+        let (best_position, lowest) = self.checked_search(starting_positions)?;
+        let (first_position, last_position) = self
+            .fuel_calculator
+            .cheapest_range(starting_positions)
+            .unwrap_or_else(|| {
+                let lower_bound_position = *starting_positions.iter().min().unwrap();
+                let upper_bound_position = *starting_positions.iter().max().unwrap();
+                let is_optimum =
+                    |position: &u64| self.total_fuel(starting_positions, *position) == Some(lowest);
+                let first_position = (lower_bound_position..best_position)
+                    .rev()
+                    .take_while(is_optimum)
+                    .last()
+                    .unwrap_or(best_position);
+                let last_position = (best_position + 1..=upper_bound_position)
+                    .take_while(is_optimum)
+                    .last()
+                    .unwrap_or(best_position);
+                (first_position, last_position)
+            });
+        Ok(Alignment::new(first_position, last_position, lowest))
+    }
+
+    fn lowest_fuel(&self, starting_positions: &[u64]) -> Result<u64, AlignmentError> {
+        self.checked_search(starting_positions)
+            .map(|(_best_position, lowest)| lowest)
+    }
+
+    fn checked_search(&self, starting_positions: &[u64]) -> Result<(u64, u64), AlignmentError> {
+        let (best_position, lowest) = self.search(starting_positions);
+        if self.check_with_brute_force {
+            let brute_force_lowest = self.find_lowest_by_brute_force(starting_positions);
+            if lowest != brute_force_lowest {
+                return Err(SearchDisagreementError::new(lowest, brute_force_lowest).into());
+            }
+        }
+        let lowest = lowest.ok_or_else(|| FuelOverflowError::new(best_position))?;
+        Ok((best_position, lowest))
+    }
+
+    /// For a convex fuel cost the total fuel is convex in the target position, so walking
+    /// downhill from the estimate reaches a cheapest position, which is never more than a step
+    /// or two away. The cheapest position never lies beyond the outermost crabs.
+    fn search(&self, starting_positions: &[u64]) -> (u64, Option<u64>) {
+        let lower_bound_position = *starting_positions.iter().min().unwrap();
+        let upper_bound_position = *starting_positions.iter().max().unwrap();
+        let mut target_position = self.fuel_calculator.estimate_optimum(starting_positions);
+        let mut lowest = self.total_fuel(starting_positions, target_position);
+        let step = |position: u64, upwards: bool| {
+            if upwards {
+                (position < upper_bound_position).then(|| position + 1)
+            } else {
+                (position > lower_bound_position).then(|| position - 1)
+            }
+        };
+
+        for upwards in [true, false] {
+            while let Some(next_position) = step(target_position, upwards) {
+                let fuel = self.total_fuel(starting_positions, next_position);
+                if fuel_rank(fuel) >= fuel_rank(lowest) {
+                    break;
+                }
+                target_position = next_position;
+                lowest = fuel;
+            }
+        }
+        (target_position, lowest)
+    }
+
+    /// `None` if the total is too much to count.
+    fn total_fuel(&self, starting_positions: &[u64], target_position: u64) -> Option<u64> {
+        starting_positions
+            .iter()
+            .try_fold(0u64, |total, starting_position| {
+                total.checked_add(
+                    self.fuel_calculator
+                        .calculate(starting_position, &target_position)?,
+                )
+            })
+    }
+
+    fn find_lowest_by_brute_force(&self, starting_positions: &[u64]) -> Option<u64> {
+        let lower_bound_position = starting_positions.iter().min().unwrap();
+        let upper_bound_position = starting_positions.iter().max().unwrap();
+
+        (*lower_bound_position..*upper_bound_position + 1)
+            .into_par_iter()
+            .map(|target_position| self.total_fuel(starting_positions, target_position))
+            .min_by_key(|fuel| fuel_rank(*fuel))
+            .unwrap()
+    }
+
+    /// The fuel used aligning at each position between the outermost crabs.
+    fn cost_curve(&self, starting_positions: &[u64]) -> Result<Vec<(u64, u64)>, FuelOverflowError> {
         let lower_bound_position = starting_positions.iter().min().unwrap();
         let upper_bound_position = starting_positions.iter().max().unwrap();
 
         (*lower_bound_position..*upper_bound_position + 1)
             .into_par_iter()
             .map(|target_position| {
-                starting_positions
-                    .iter()
-                    .map(|starting_position| {
-                        self.fuel_calculator
-                            .calculate(starting_position, &target_position)
-                    })
-                    .sum()
+                self.total_fuel(starting_positions, target_position)
+                    .map(|fuel| (target_position, fuel))
+                    .ok_or_else(|| FuelOverflowError::new(target_position))
             })
-            .min()
-            .unwrap()
+            .collect()
     }
 }
 
 #[derive(derive_new::new)]
-pub(crate) struct Day7SolutionExecutor;
+pub(crate) struct Day7SolutionExecutor {
+    #[new(default)]
+    parameters: AlignmentParameters,
+}
+
+impl Day7SolutionExecutor {
+    pub(crate) fn with_parameters(parameters: AlignmentParameters) -> Self {
+        Day7SolutionExecutor { parameters }
+    }
+
+    pub(crate) fn alignment(
+        &self,
+        input: Vec<u64>,
+        fuel_cost: &FuelCost,
+    ) -> Result<Alignment, AlignmentError> {
+        self.finder(fuel_cost).find_optimum(&input)
+    }
+
+    /// The fuel used aligning at each position between the outermost crabs, in position order.
+    pub(crate) fn cost_curve(
+        &self,
+        input: Vec<u64>,
+        fuel_cost: &FuelCost,
+    ) -> Result<Vec<(u64, u64)>, FuelOverflowError> {
+        self.finder(fuel_cost).cost_curve(&input)
+    }
+
+    /// Whether the fast search is sure to find the cheapest position for these crabs. If not, the
+    /// brute force check can confirm its answers.
+    pub(crate) fn is_convex(&self, input: &[u64], fuel_cost: &FuelCost) -> bool {
+        let spread = input.iter().max().unwrap_or(&0) - input.iter().min().unwrap_or(&0);
+        fuel_cost.calculator().is_convex_up_to(spread)
+    }
+
+    fn finder(&self, fuel_cost: &FuelCost) -> OptimumUsageFinder {
+        OptimumUsageFinder::new(
+            fuel_cost.calculator(),
+            self.parameters.check_with_brute_force,
+        )
+    }
+}
 
 impl SolutionExecutor for Day7SolutionExecutor {
     type Input = Vec<u64>;
-    type Part1Output = u64;
-    type Part2Output = u64;
+    type Part1Output = Result<u64, AlignmentError>;
+    type Part2Output = Result<u64, AlignmentError>;
 
     fn part_1(&self, input: Self::Input) -> Self::Part1Output {
-        OptimumUsageFinder::new(SimpleFuelCalculator::new()).find_lowest(input)
+        self.finder(&self.parameters.part_1_fuel_cost)
+            .lowest_fuel(&input)
     }
 
     fn part_2(&self, input: Self::Input) -> Self::Part2Output {
-        OptimumUsageFinder::new(AccurateFuelCalculator::new()).find_lowest(input)
+        self.finder(&self.parameters.part_2_fuel_cost)
+            .lowest_fuel(&input)
     }
 }
 
@@ -73,6 +462,8 @@ impl SolutionExecutor for Day7SolutionExecutor {
 mod tests {
     use speculoos::prelude::*;
 
+    use crate::domain::solution_executor::test_random::TestRandom;
+
     use super::*;
 
     fn test_data() -> Vec<u64> {
@@ -81,11 +472,164 @@ mod tests {
 
     #[test]
     fn calculates_least_amount_of_fuel_to_align_at_common_position() {
-        assert_that(&Day7SolutionExecutor::new().part_1(test_data())).is_equal_to(37);
+        assert_that(&Day7SolutionExecutor::new().part_1(test_data())).is_equal_to(Ok(37));
     }
 
     #[test]
     fn calculates_least_amount_of_fuel_to_align_at_common_position_with_increasing_fuel_cost() {
-        assert_that(&Day7SolutionExecutor::new().part_2(test_data())).is_equal_to(168);
+        assert_that(&Day7SolutionExecutor::new().part_2(test_data())).is_equal_to(Ok(168));
+    }
+
+    #[test]
+    fn finds_cheapest_position() {
+        let executor = Day7SolutionExecutor::new();
+        assert_that(&executor.alignment(test_data(), &FuelCost::Linear))
+            .is_equal_to(Ok(Alignment::new(2, 2, 37)));
+        assert_that(&executor.alignment(test_data(), &FuelCost::Triangular))
+            .is_equal_to(Ok(Alignment::new(5, 5, 168)));
+    }
+
+    #[test]
+    fn finds_every_cheapest_position() {
+        let alignment = Day7SolutionExecutor::new()
+            .alignment(vec![1, 4], &FuelCost::Linear)
+            .unwrap();
+        assert_that(&alignment.to_string())
+            .is_equal_to("align at positions 1 to 4 for 3 fuel".to_string());
+    }
+
+    #[test]
+    fn finds_wide_range_of_cheapest_positions_without_trying_them() {
+        let input = [vec![0; 500], vec![20_000_000; 500]].concat();
+        let executor = Day7SolutionExecutor::new();
+        let cheapest = Ok(Alignment::new(0, 20_000_000, 10_000_000_000));
+
+        assert_that(&executor.part_1(input.clone())).is_equal_to(Ok(10_000_000_000));
+        assert_that(&executor.alignment(input.clone(), &FuelCost::Linear)).is_equal_to(&cheapest);
+        assert_that(&executor.alignment(input.clone(), &FuelCost::Polynomial(vec![0, 1])))
+            .is_equal_to(&cheapest);
+        assert_that(&executor.alignment(input, &FuelCost::Polynomial(vec![3])))
+            .is_equal_to(Ok(Alignment::new(0, 20_000_000, 3000)));
+    }
+
+    #[test]
+    fn calculates_fuel_for_every_position() {
+        let cost_curve = Day7SolutionExecutor::new()
+            .cost_curve(test_data(), &FuelCost::Linear)
+            .unwrap();
+        assert_that(&cost_curve.len()).is_equal_to(17);
+        assert_that(&cost_curve[1]).is_equal_to((1, 41));
+        assert_that(&cost_curve[2]).is_equal_to((2, 37));
+        assert_that(&cost_curve[3]).is_equal_to((3, 39));
+        assert_that(&cost_curve[10]).is_equal_to((10, 71));
+    }
+
+    fn generated_positions(count: usize, seed: u64) -> Vec<u64> {
+        let mut random = TestRandom::new(seed);
+        (0..count)
+            .map(|_crab| {
+                // squaring skews the crabs towards low positions so the median and mean differ
+                let position = random.below(1000);
+                position * position / 500
+            })
+            .collect()
+    }
+
+    fn checked_executor() -> Day7SolutionExecutor {
+        Day7SolutionExecutor::with_parameters(AlignmentParameters::new(
+            FuelCost::Linear,
+            FuelCost::Triangular,
+            true,
+        ))
+    }
+
+    #[test]
+    fn fast_search_agrees_with_brute_force_on_generated_positions() {
+        let executor = checked_executor();
+        (0..10).for_each(|seed| {
+            executor
+                .part_1(generated_positions(1000 + seed as usize, seed))
+                .unwrap();
+            executor
+                .part_2(generated_positions(1000 + seed as usize, seed))
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn reports_fast_search_disagreeing_with_brute_force() {
+        let executor = checked_executor();
+        let concave = FuelCost::Polynomial(vec![0, 10, -1]);
+
+        assert_that(&executor.alignment(vec![0, 1, 2, 3, 12], &concave)).is_err_containing(
+            AlignmentError::SearchDisagreement(SearchDisagreementError::new(Some(34), Some(9))),
+        );
+    }
+
+    #[test]
+    fn finds_lowest_fuel_for_a_single_crab() {
+        let executor = checked_executor();
+        assert_that(&executor.part_1(vec![5])).is_equal_to(Ok(0));
+        assert_that(&executor.part_2(vec![5])).is_equal_to(Ok(0));
+    }
+
+    #[test]
+    fn finds_lowest_fuel_with_polynomial_costs() {
+        let executor = checked_executor();
+        let linear = FuelCost::Polynomial(vec![0, 1]);
+        let quadratic = FuelCost::Polynomial(vec![0, 0, 1]);
+        let cubic = FuelCost::Polynomial(vec![0, 0, 0, 1]);
+
+        assert_that(&executor.alignment(test_data(), &linear).unwrap().fuel()).is_equal_to(&37);
+        assert_that(&executor.alignment(test_data(), &quadratic))
+            .is_equal_to(Ok(Alignment::new(5, 5, 291)));
+        (0..5).for_each(|seed| {
+            executor
+                .alignment(generated_positions(500, seed), &cubic)
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn detects_non_convex_fuel_costs() {
+        let executor = Day7SolutionExecutor::new();
+        assert_that(&executor.is_convex(&test_data(), &FuelCost::Triangular)).is_true();
+        assert_that(&executor.is_convex(&test_data(), &FuelCost::Polynomial(vec![0, 0, 1])))
+            .is_true();
+        assert_that(&executor.is_convex(&test_data(), &FuelCost::Polynomial(vec![0, 10, -1])))
+            .is_false();
+        assert_that(&executor.is_convex(&test_data(), &FuelCost::Polynomial(vec![5, -1])))
+            .is_false();
+    }
+
+    #[test]
+    fn reports_fuel_too_large_to_count() {
+        let executor = Day7SolutionExecutor::new();
+        let cubic = FuelCost::Polynomial(vec![0, 0, 0, 1]);
+
+        assert_that(&executor.alignment(vec![0, 0, 5_000_000], &cubic)).is_err();
+        assert_that(&executor.part_2(vec![0, u64::MAX])).is_err();
+    }
+
+    #[test]
+    fn checks_convexity_without_trying_every_distance() {
+        let executor = Day7SolutionExecutor::new();
+        let input = [0, 10_000_000_000];
+
+        assert_that(&executor.is_convex(&input, &FuelCost::Linear)).is_true();
+        assert_that(&executor.is_convex(&input, &FuelCost::Triangular)).is_true();
+        assert_that(&executor.is_convex(&input, &FuelCost::Polynomial(vec![0, 0, 0, 1]))).is_true();
+        assert_that(&executor.is_convex(&input, &FuelCost::Polynomial(vec![0, 0, 0, -1])))
+            .is_false();
+    }
+
+    #[test]
+    fn checks_convexity_of_costs_too_large_to_count() {
+        let executor = Day7SolutionExecutor::new();
+        let input = [0, 1, 3_000_000];
+
+        assert_that(&executor.is_convex(&input, &FuelCost::Polynomial(vec![0, 0, 0, 1]))).is_true();
+        assert_that(&executor.is_convex(&input, &FuelCost::Polynomial(vec![0, 0, 0, -1])))
+            .is_false();
     }
 }
@@ -1,17 +1,60 @@
+use std::fmt::{Display, Formatter};
+
 use itertools::Itertools;
 
+use crate::domain::grid::{Adjacency, Grid, GridPosition};
 use crate::domain::solution_executor::SolutionExecutor;
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum LowPointRule {
+    /// Lower than every neighbour.
+    Strict,
+    /// No higher than any neighbour, so every cell of a flat bottom counts.
+    Plateau,
+}
+
+#[derive(Debug, Copy, Clone, derive_new::new, derive_getters::Getters)]
+pub(crate) struct TerrainParameters {
+    /// Which neighbours a basin flows between and a low point is compared with.
+    adjacency: Adjacency,
+    /// Locations at least this high are walls between basins, and are never low points.
+    wall_height: u8,
+    low_point_rule: LowPointRule,
+}
+
+impl Default for TerrainParameters {
+    fn default() -> Self {
+        TerrainParameters::new(Adjacency::FourWay, 9, LowPointRule::Strict)
+    }
+}
+
 #[derive(derive_new::new)]
-pub(crate) struct Day9SolutionExecutor;
+pub(crate) struct Day9SolutionExecutor {
+    #[new(default)]
+    parameters: TerrainParameters,
+}
+
+impl Day9SolutionExecutor {
+    pub(crate) fn with_parameters(parameters: TerrainParameters) -> Self {
+        Day9SolutionExecutor { parameters }
+    }
+
+    pub(crate) fn basin_map(&self, input: Grid<u8>) -> BasinMap {
+        self.to_height_map(input).basin_map()
+    }
+
+    fn to_height_map(&self, heights: Grid<u8>) -> HeightMap {
+        HeightMap::new(heights.map(Location::new), self.parameters)
+    }
+}
 
 impl SolutionExecutor for Day9SolutionExecutor {
-    type Input = Vec<Vec<u8>>;
+    type Input = Grid<u8>;
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn part_1(&self, input: Self::Input) -> Self::Part1Output {
-        let height_map = HeightMap::new(to_locations(input));
+        let height_map = self.to_height_map(input);
         height_map
             .low_points()
             .into_iter()
@@ -20,10 +63,12 @@ impl SolutionExecutor for Day9SolutionExecutor {
     }
 
     fn part_2(&self, input: Self::Input) -> Self::Part2Output {
-        let height_map = HeightMap::new(to_locations(input));
+        let height_map = self.to_height_map(input);
         height_map
-            .basin_sizes()
-            .into_iter()
+            .basin_map()
+            .basins()
+            .iter()
+            .map(|basin| *basin.size())
             .sorted_by(|a, b| Ord::cmp(b, a))
             .take(3)
             .product()
@@ -32,148 +77,200 @@ impl SolutionExecutor for Day9SolutionExecutor {
 
 #[derive(derive_new::new)]
 struct HeightMap {
-    inner: Vec<Vec<Location>>,
+    inner: Grid<Location>,
+    parameters: TerrainParameters,
 }
 
 impl HeightMap {
-    fn low_points(&self) -> Vec<(Position, &Location)> {
+    fn low_points(&self) -> Vec<(GridPosition, &Location)> {
         self.inner
-            .iter()
-            .enumerate()
-            .flat_map(|(row_index, row)| {
-                row.iter()
-                    .enumerate()
-                    .map(|(col_index, location)| (Position::new(row_index, col_index), location))
-                    .filter(|(position, _location)| self.is_low_point(position))
-                    .collect::<Vec<(Position, &Location)>>()
-            })
+            .cells()
+            .filter(|(position, _location)| self.is_low_point(*position))
             .collect()
     }
 
-    fn is_low_point(&self, position: &Position) -> bool {
+    fn is_low_point(&self, position: GridPosition) -> bool {
         let height = self
             .at_position(position)
             .expect("low point position does not exist")
             .height();
 
-        let neighbour_heights = self.neighbours(position).into_iter().map(Location::height);
+        let mut neighbour_heights = self.neighbours(position).into_iter().map(Location::height);
 
-        height < neighbour_heights.min().unwrap()
+        !self.is_high_point(position)
+            && match self.parameters.low_point_rule {
+                LowPointRule::Strict => neighbour_heights.all(|neighbour| height < neighbour),
+                LowPointRule::Plateau => neighbour_heights.all(|neighbour| height <= neighbour),
+            }
     }
 
-    fn is_high_point(&self, position: &Position) -> bool {
+    fn is_high_point(&self, position: GridPosition) -> bool {
         self.at_position(position)
             .expect("high point position does not exist")
             .height()
-            == 9
-    }
-
-    fn at_position(&self, position: &Position) -> Option<&Location> {
-        self.inner
-            .get(position.row)
-            .and_then(|row| row.get(position.col))
+            >= self.parameters.wall_height
     }
 
-    fn is_valid_position(&self, position: &Position) -> bool {
-        self.at_position(position).is_some()
+    fn at_position(&self, position: GridPosition) -> Option<&Location> {
+        self.inner.get(position)
     }
 
-    fn neighbours(&self, position: &Position) -> Vec<&Location> {
-        position
-            .adjacent()
-            .into_iter()
-            .flat_map(|p| self.at_position(&p))
+    fn neighbours(&self, position: GridPosition) -> Vec<&Location> {
+        self.inner
+            .neighbours(position, self.parameters.adjacency)
+            .flat_map(|p| self.at_position(p))
             .collect()
     }
 
-    fn basin_sizes(&self) -> Vec<usize> {
+    /// Gives every cell that flows down to a low point the id of that low point's basin, numbering
+    /// basins in the order of their low points. The labels double as the record of visited cells,
+    /// so each cell is explored once and the whole map is labelled in linear time. A low point
+    /// already reached from an earlier one, as on a plateau, belongs to that earlier basin.
+    fn basin_map(&self) -> BasinMap {
+        let mut labels: Grid<Option<usize>> = self.inner.clone().map(|_location| None);
+        let mut basins: Vec<Basin> = Vec::new();
+
         self.low_points()
             .into_iter()
-            .map(|(position, _location)| self.basin_size(position))
-            .collect()
+            .for_each(|(low_point, _location)| {
+                if labels.get(low_point).unwrap().is_some() {
+                    return;
+                }
+                let id = basins.len();
+                let mut basin = Basin::new(id, low_point);
+                let mut unexplored: Vec<GridPosition> = vec![low_point];
+                *labels.get_mut(low_point).unwrap() = Some(id);
+
+                while let Some(current) = unexplored.pop() {
+                    basin.add(current);
+                    self.inner
+                        .neighbours(current, self.parameters.adjacency)
+                        .filter(|position| !self.is_high_point(*position))
+                        .for_each(|position| {
+                            let label = labels.get_mut(position).unwrap();
+                            if label.is_none() {
+                                *label = Some(id);
+                                unexplored.push(position);
+                            }
+                        });
+                }
+                basins.push(basin);
+            });
+
+        BasinMap::new(labels, basins)
     }
+}
 
-    fn basin_size(&self, low_point: Position) -> usize {
-        let mut explored_positions: Vec<Position> = Vec::new();
-        let mut unexplored_forks: Vec<Position> = vec![low_point];
-
-        while let Some(current) = unexplored_forks.pop() {
-            explored_positions.push(current);
-            let new_positions: Vec<Position> = current
-                .adjacent()
-                .into_iter()
-                .filter(|position| self.is_valid_position(position))
-                .filter(|position| !self.is_high_point(position))
-                .filter(|position| !explored_positions.contains(position))
-                .filter(|position| !unexplored_forks.contains(position))
-                .collect();
+#[derive(Clone, derive_new::new)]
+struct Location {
+    height: u8,
+}
 
-            unexplored_forks.extend(new_positions);
-        }
+impl Location {
+    fn risk_level(&self) -> usize {
+        self.height as usize + 1
+    }
 
-        explored_positions.len()
+    fn height(&self) -> u8 {
+        self.height
     }
 }
 
-#[derive(derive_new::new, Copy, Clone, Debug, Eq, PartialEq)]
-struct Position {
-    row: usize,
-    col: usize,
+#[derive(Debug, Copy, Clone, Eq, PartialEq, derive_new::new, derive_getters::Getters)]
+pub(crate) struct BoundingBox {
+    top_left: GridPosition,
+    bottom_right: GridPosition,
 }
 
-impl Position {
-    fn adjacent(&self) -> Vec<Position> {
-        vec![self.above(), self.below(), self.left(), self.right()]
-            .into_iter()
-            .flatten()
-            .collect()
+impl BoundingBox {
+    fn extend(&mut self, position: GridPosition) {
+        self.top_left = GridPosition::new(
+            self.top_left.row().min(position.row()),
+            self.top_left.col().min(position.col()),
+        );
+        self.bottom_right = GridPosition::new(
+            self.bottom_right.row().max(position.row()),
+            self.bottom_right.col().max(position.col()),
+        );
     }
+}
 
-    fn above(&self) -> Option<Position> {
-        self.row
-            .checked_add(1)
-            .map(|row| Position::new(row, self.col))
-    }
+#[derive(Debug, Clone, Eq, PartialEq, derive_getters::Getters)]
+pub(crate) struct Basin {
+    id: usize,
+    low_point: GridPosition,
+    size: usize,
+    bounding_box: BoundingBox,
+}
 
-    fn below(&self) -> Option<Position> {
-        self.row
-            .checked_sub(1)
-            .map(|row| Position::new(row, self.col))
+impl Basin {
+    fn new(id: usize, low_point: GridPosition) -> Self {
+        Basin {
+            id,
+            low_point,
+            size: 0,
+            bounding_box: BoundingBox::new(low_point, low_point),
+        }
     }
 
-    fn left(&self) -> Option<Position> {
-        self.col
-            .checked_sub(1)
-            .map(|col| Position::new(self.row, col))
+    fn add(&mut self, position: GridPosition) {
+        self.size += 1;
+        self.bounding_box.extend(position);
     }
 
-    fn right(&self) -> Option<Position> {
-        self.col
-            .checked_add(1)
-            .map(|col| Position::new(self.row, col))
+    /// Basins are lettered A to Z, then a to z, after which letters are reused.
+    pub(crate) fn letter(&self) -> char {
+        let letters: Vec<char> = ('A'..='Z').chain('a'..='z').collect();
+        letters[self.id % letters.len()]
     }
 }
 
-#[derive(derive_new::new)]
-struct Location {
-    height: u8,
+impl Display for Basin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "basin {}: {} cells, low point at row {} column {}, rows {} to {}, columns {} to {}",
+            self.letter(),
+            self.size,
+            self.low_point.row(),
+            self.low_point.col(),
+            self.bounding_box.top_left.row(),
+            self.bounding_box.bottom_right.row(),
+            self.bounding_box.top_left.col(),
+            self.bounding_box.bottom_right.col()
+        )
+    }
 }
 
-impl Location {
-    fn risk_level(&self) -> usize {
-        self.height as usize + 1
-    }
+/// Every cell labelled with the id of the basin it belongs to. Walls, and any cell that does not
+/// flow down to a low point, have no label.
+#[derive(Debug, derive_new::new, derive_getters::Getters)]
+pub(crate) struct BasinMap {
+    labels: Grid<Option<usize>>,
+    basins: Vec<Basin>,
+}
 
-    fn height(&self) -> u8 {
-        self.height
+impl BasinMap {
+    /// Draws each basin's cells as its letter, passed through `paint` with the basin, and every
+    /// other cell as `.`, followed by a line describing each basin.
+    pub(crate) fn render(&self, paint: impl Fn(&Basin, char) -> String) -> String {
+        let grid = self.labels.display_with(|_position, label| match label {
+            Some(id) => {
+                let basin = &self.basins[*id];
+                paint(basin, basin.letter())
+            }
+            None => ".".to_string(),
+        });
+        std::iter::once(grid)
+            .chain(self.basins.iter().map(ToString::to_string))
+            .join("\n")
     }
 }
 
-fn to_locations(heights: Vec<Vec<u8>>) -> Vec<Vec<Location>> {
-    heights
-        .into_iter()
-        .map(|line| line.into_iter().map(Location::new).collect())
-        .collect()
+impl Display for BasinMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|_basin, letter| letter.to_string()))
+    }
 }
 
 #[cfg(test)]
@@ -182,14 +279,15 @@ mod tests {
 
     use super::*;
 
-    fn test_data() -> Vec<Vec<u8>> {
-        vec![
+    fn test_data() -> Grid<u8> {
+        Grid::new(vec![
             vec![2, 1, 9, 9, 9, 4, 3, 2, 1, 0],
             vec![3, 9, 8, 7, 8, 9, 4, 9, 2, 1],
             vec![9, 8, 5, 6, 7, 8, 9, 8, 9, 2],
             vec![8, 7, 6, 7, 8, 9, 6, 7, 8, 9],
             vec![9, 8, 9, 9, 9, 6, 5, 6, 7, 8],
-        ]
+        ])
+        .unwrap()
     }
 
     #[test]
@@ -201,4 +299,110 @@ mod tests {
     fn multiplies_size_of_three_largest_basins() {
         assert_that(&Day9SolutionExecutor::new().part_2(test_data())).is_equal_to(1134);
     }
+
+    #[test]
+    fn labels_each_basin_with_a_letter() {
+        let basin_map = Day9SolutionExecutor::new().basin_map(test_data());
+        assert_that(&basin_map.to_string().lines().take(5).join("\n"))
+            .is_equal_to("AA...BBBBB\nA.CCC.B.BB\n.CCCCC.D.B\nCCCCC.DDD.\n.C...DDDDD".to_string());
+    }
+
+    #[test]
+    fn describes_each_basin() {
+        let basin_map = Day9SolutionExecutor::new().basin_map(test_data());
+        let basin = |low_point: (usize, usize),
+                     size,
+                     top_left: (usize, usize),
+                     bottom_right: (usize, usize)| {
+            (
+                GridPosition::new(low_point.0, low_point.1),
+                size,
+                BoundingBox::new(
+                    GridPosition::new(top_left.0, top_left.1),
+                    GridPosition::new(bottom_right.0, bottom_right.1),
+                ),
+            )
+        };
+        let basins: Vec<(GridPosition, usize, BoundingBox)> = basin_map
+            .basins()
+            .iter()
+            .map(|basin| (*basin.low_point(), *basin.size(), *basin.bounding_box()))
+            .collect();
+
+        assert_that(&basins).is_equal_to(vec![
+            basin((0, 1), 3, (0, 0), (1, 1)),
+            basin((0, 9), 9, (0, 5), (2, 9)),
+            basin((2, 2), 14, (1, 0), (4, 5)),
+            basin((4, 6), 9, (2, 5), (4, 9)),
+        ]);
+    }
+
+    #[test]
+    fn renders_basin_map_with_descriptions() {
+        let basin_map = Day9SolutionExecutor::new().basin_map(test_data());
+        let rendered = basin_map.render(|basin, letter| format!("{}{}", letter, basin.id()));
+        assert_that(&rendered.lines().next()).is_equal_to(Some("A0A0...B1B1B1B1B1"));
+        assert_that(&rendered.lines().nth(5)).is_equal_to(Some(
+            "basin A: 3 cells, low point at row 0 column 1, rows 0 to 1, columns 0 to 1",
+        ));
+    }
+
+    #[test]
+    fn sizes_a_huge_basin() {
+        let size = 500;
+        let centre = size / 2;
+        let heights = Grid::new(
+            (0..size)
+                .map(|row: usize| {
+                    (0..size)
+                        .map(|col: usize| {
+                            (row.abs_diff(centre) + col.abs_diff(centre)).min(8) as u8
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+        .unwrap();
+
+        assert_that(&Day9SolutionExecutor::new().part_2(heights)).is_equal_to(size * size);
+    }
+
+    fn terrain_executor(
+        adjacency: Adjacency,
+        wall_height: u8,
+        low_point_rule: LowPointRule,
+    ) -> Day9SolutionExecutor {
+        Day9SolutionExecutor::with_parameters(TerrainParameters::new(
+            adjacency,
+            wall_height,
+            low_point_rule,
+        ))
+    }
+
+    #[test]
+    fn joins_basins_diagonally_with_eight_way_adjacency() {
+        let executor = terrain_executor(Adjacency::EightWay, 9, LowPointRule::Strict);
+        assert_that(&executor.basin_map(test_data()).to_string().lines().next())
+            .is_equal_to(Some("AA...AAAAA"));
+    }
+
+    #[test]
+    fn treats_lower_locations_as_walls() {
+        let executor = terrain_executor(Adjacency::FourWay, 7, LowPointRule::Strict);
+        let input = Grid::new(vec![vec![0, 7, 1], vec![2, 8, 3]]).unwrap();
+        assert_that(&executor.basin_map(input.clone()).to_string().lines().next())
+            .is_equal_to(Some("A.B"));
+        assert_that(&executor.part_1(input)).is_equal_to(3);
+    }
+
+    #[test]
+    fn finds_plateau_low_points() {
+        let input = Grid::new(vec![vec![1, 1, 2], vec![1, 3, 4]]).unwrap();
+        let strict = terrain_executor(Adjacency::FourWay, 9, LowPointRule::Strict);
+        let plateau = terrain_executor(Adjacency::FourWay, 9, LowPointRule::Plateau);
+
+        assert_that(&strict.part_1(input.clone())).is_equal_to(0);
+        assert_that(&plateau.part_1(input.clone())).is_equal_to(6);
+        assert_that(&plateau.part_2(input)).is_equal_to(6);
+    }
 }
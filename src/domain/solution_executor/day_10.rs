@@ -1,176 +1,516 @@
-use itertools::FoldWhile::{Continue, Done};
+use std::fmt::{Display, Formatter};
+use std::io::{self, BufRead};
+
 use itertools::Itertools;
 
 use crate::domain::solution_executor::SolutionExecutor;
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq, derive_new::new)]
+pub(crate) struct BracketPair {
+    opener: char,
+    closer: char,
+    /// Score for a corrupted line that wrongly uses this closer.
+    error_score: u64,
+    /// Score for each of this closer in a completion.
+    completion_score: u64,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+pub(crate) struct SyntaxConfiguration {
+    pairs: Vec<BracketPair>,
+    /// A completion's score so far is multiplied by this before each closer's score is added.
+    completion_multiplier: u64,
+}
+
+impl SyntaxConfiguration {
+    pub(crate) fn with_pairs(pairs: Vec<BracketPair>) -> Result<Self, RepeatedBracketError> {
+        if let Some(character) = pairs
+            .iter()
+            .flat_map(|pair| [pair.opener, pair.closer])
+            .duplicates()
+            .next()
+        {
+            return Err(RepeatedBracketError::new(character));
+        }
+        Ok(SyntaxConfiguration {
+            pairs,
+            ..SyntaxConfiguration::default()
+        })
+    }
+
+    fn opened_by(&self, character: char) -> Option<&BracketPair> {
+        self.pairs.iter().find(|pair| pair.opener == character)
+    }
+
+    fn closed_by(&self, character: char) -> Option<&BracketPair> {
+        self.pairs.iter().find(|pair| pair.closer == character)
+    }
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new, Eq, PartialEq)]
+#[error("bracket {character} appears in more than one pair")]
+pub(crate) struct RepeatedBracketError {
+    character: char,
+}
+
+impl Default for SyntaxConfiguration {
+    fn default() -> Self {
+        SyntaxConfiguration::new(
+            vec![
+                BracketPair::new('(', ')', 3, 1),
+                BracketPair::new('[', ']', 57, 2),
+                BracketPair::new('{', '}', 1197, 3),
+                BracketPair::new('<', '>', 25137, 4),
+            ],
+            5,
+        )
+    }
+}
+
 #[derive(derive_new::new)]
-pub(crate) struct Day10SolutionExecutor;
+pub(crate) struct Day10SolutionExecutor {
+    #[new(default)]
+    configuration: SyntaxConfiguration,
+}
+
+impl Day10SolutionExecutor {
+    pub(crate) fn with_configuration(configuration: SyntaxConfiguration) -> Self {
+        Day10SolutionExecutor { configuration }
+    }
+
+    /// Explains where each corrupted line goes wrong.
+    pub(crate) fn diagnostics(&self, input: Vec<String>) -> Vec<SyntaxDiagnostic> {
+        let syntax_checker = SyntaxChecker::new(&self.configuration);
+
+        input
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, line)| {
+                syntax_checker
+                    .check_line(&line)
+                    .err()
+                    .map(|error| SyntaxDiagnostic::new(index + 1, line, error))
+            })
+            .collect()
+    }
+
+    /// Appends its completion to each line that is not corrupted, so that every repaired line is
+    /// complete.
+    pub(crate) fn repair(&self, input: Vec<String>) -> Vec<RepairedLine> {
+        let syntax_checker = SyntaxChecker::new(&self.configuration);
+
+        input
+            .into_iter()
+            .map(|line| match syntax_checker.repair_line(&line) {
+                Ok(repaired) => RepairedLine::Repaired(repaired),
+                Err(error) => RepairedLine::Unrepairable { line, error },
+            })
+            .collect()
+    }
+
+    /// Scores both parts in one pass, keeping the open brackets and one `u64` per incomplete line.
+    pub(crate) fn check_stream(
+        &self,
+        mut reader: impl BufRead,
+    ) -> Result<SyntaxTotals, StreamCheckError> {
+        let mut checker = StreamingSyntaxChecker::new(&self.configuration);
+        loop {
+            let buffer = reader.fill_buf()?;
+            if buffer.is_empty() {
+                return Ok(checker.finish()?);
+            }
+            buffer.iter().try_for_each(|&byte| checker.feed(byte))?;
+            let length = buffer.len();
+            reader.consume(length);
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new, Eq, PartialEq)]
+#[error("the completion score of line {line_number} is too large to count")]
+pub(crate) struct CompletionScoreOverflowError {
+    line_number: usize,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum StreamCheckError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    CompletionScoreOverflow(#[from] CompletionScoreOverflowError),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum RepairedLine {
+    /// A line with its completion appended, or unchanged if it was already complete.
+    Repaired(String),
+    /// A line that cannot be repaired by appending closers.
+    Unrepairable { line: String, error: SyntaxError },
+}
+
+impl Display for RepairedLine {
+    /// Lines that cannot be repaired are flagged with a leading `#`, the kind of their first error
+    /// and its column.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepairedLine::Repaired(line) => write!(f, "{}", line),
+            RepairedLine::Unrepairable {
+                line,
+                error: SyntaxError::Corrupted { column, .. },
+            } => write!(f, "# corrupted at column {}: {}", column, line),
+            RepairedLine::Unrepairable {
+                line,
+                error: SyntaxError::UnknownCharacter { column, found },
+            } => write!(
+                f,
+                "# unknown character {} at column {}: {}",
+                found, column, line
+            ),
+        }
+    }
+}
+
+/// The answers to both parts, from one pass over the input.
+#[derive(Debug, Eq, PartialEq, derive_new::new)]
+pub(crate) struct SyntaxTotals {
+    syntax_error_score: u64,
+    /// `None` if no line is incomplete.
+    middle_completion_score: Option<u64>,
+}
+
+impl Display for SyntaxTotals {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "part 1: {}", self.syntax_error_score)?;
+        match self.middle_completion_score {
+            Some(score) => write!(f, "part 2: {}", score),
+            None => write!(f, "part 2: no incomplete lines"),
+        }
+    }
+}
 
 impl SolutionExecutor for Day10SolutionExecutor {
     type Input = Vec<String>;
     type Part1Output = u64;
-    type Part2Output = u64;
+    type Part2Output = Result<u64, CompletionScoreOverflowError>;
 
     fn part_1(&self, input: Self::Input) -> Self::Part1Output {
-        let syntax_checker = SyntaxChecker::new();
-        let error_scorer = SyntaxErrorScorer::new();
+        let syntax_checker = SyntaxChecker::new(&self.configuration);
+        let error_scorer = SyntaxErrorScorer::new(&self.configuration);
 
-        to_syntax_lines(input)
-            .into_iter()
+        input
+            .iter()
             .flat_map(|line| syntax_checker.check_line(line).err())
             .map(|error| error_scorer.score(&error))
             .sum()
     }
 
     fn part_2(&self, input: Self::Input) -> Self::Part2Output {
-        let syntax_checker = SyntaxChecker::new();
-        let completion_scorer = SyntaxCompletionScorer::new();
+        let syntax_checker = SyntaxChecker::new(&self.configuration);
+        let completion_scorer = SyntaxCompletionScorer::new(&self.configuration);
 
-        let completion_scores: Vec<u64> = to_syntax_lines(input)
+        let completion_scores: Vec<u64> = input
+            .iter()
+            .enumerate()
+            .filter(|(_index, line)| syntax_checker.check_line(line).is_ok())
+            .map(|(index, line)| {
+                completion_scorer
+                    .score(syntax_checker.complete_incomplete_line(line))
+                    .ok_or_else(|| CompletionScoreOverflowError::new(index + 1))
+            })
+            .collect::<Result<Vec<u64>, CompletionScoreOverflowError>>()?
             .into_iter()
-            .filter(|line| syntax_checker.check_line(line.clone()).is_ok())
-            .map(|line| completion_scorer.score(syntax_checker.complete_incomplete_line(line)))
             .sorted()
             .collect();
 
-        *completion_scores.get(completion_scores.len() / 2).unwrap() as u64
+        Ok(*completion_scores.get(completion_scores.len() / 2).unwrap())
     }
 }
 
-fn to_syntax_lines(lines: Vec<String>) -> Vec<Vec<SyntaxCharacter>> {
-    lines
-        .into_iter()
-        .map(|line| line.chars().map(SyntaxCharacter::new).collect())
-        .collect()
-}
-
 #[derive(derive_new::new)]
-struct SyntaxChecker;
+struct SyntaxChecker<'a> {
+    configuration: &'a SyntaxConfiguration,
+}
 
-impl SyntaxChecker {
-    fn check_line(&self, line: Vec<SyntaxCharacter>) -> Result<(), SyntaxError> {
-        SyntaxWalker::new().walk(line)
+impl SyntaxChecker<'_> {
+    fn check_line(&self, line: &str) -> Result<(), SyntaxError> {
+        SyntaxWalker::new(self.configuration).walk(line)
     }
 
-    fn complete_incomplete_line(&self, line: Vec<SyntaxCharacter>) -> Vec<SyntaxCharacter> {
-        let mut walker = SyntaxWalker::new();
+    fn complete_incomplete_line(&self, line: &str) -> Vec<BracketPair> {
+        let mut walker = SyntaxWalker::new(self.configuration);
         walker.walk(line).unwrap();
         walker.suggest_completion()
     }
+
+    fn repair_line(&self, line: &str) -> Result<String, SyntaxError> {
+        let mut walker = SyntaxWalker::new(self.configuration);
+        walker.walk(line)?;
+        let completion = walker
+            .suggest_completion()
+            .into_iter()
+            .map(|pair| pair.closer);
+        Ok(line.chars().chain(completion).collect())
+    }
 }
 
-#[derive(derive_new::new, Copy, Clone, Eq, PartialEq, Debug)]
-struct SyntaxCharacter(char);
+struct SyntaxWalker<'a> {
+    configuration: &'a SyntaxConfiguration,
+    /// Open pairs with the column they were opened at.
+    syntax_stack: Vec<(usize, BracketPair)>,
+}
 
-impl SyntaxCharacter {
-    fn is_closed_by(&self, other: Self) -> bool {
-        other == self.closing_character()
+impl<'a> SyntaxWalker<'a> {
+    fn new(configuration: &'a SyntaxConfiguration) -> Self {
+        SyntaxWalker {
+            configuration,
+            syntax_stack: Vec::new(),
+        }
     }
 
-    fn closing_character(&self) -> Self {
-        let character = match self.value() {
-            '(' => ')',
-            '[' => ']',
-            '{' => '}',
-            '<' => '>',
-            _ => unimplemented!(),
-        };
-        SyntaxCharacter::new(character)
+    fn walk(&mut self, syntax: &str) -> Result<(), SyntaxError> {
+        syntax
+            .chars()
+            .zip(1..)
+            .try_for_each(|(character, column)| self.step(character, column))
     }
 
-    fn closes(&self) -> bool {
-        [')', ']', '}', '>'].contains(&self.0)
+    fn step(&mut self, character: char, column: usize) -> Result<(), SyntaxError> {
+        if let Some(pair) = self.configuration.opened_by(character) {
+            self.syntax_stack.push((column, *pair));
+            return Ok(());
+        }
+        if self.configuration.closed_by(character).is_none() {
+            return Err(SyntaxError::UnknownCharacter {
+                column,
+                found: character,
+            });
+        }
+
+        match self.syntax_stack.last() {
+            Some((_opened_at, open_pair)) if open_pair.closer == character => {
+                self.syntax_stack.pop();
+                Ok(())
+            }
+            unmatched_opener => Err(SyntaxError::Corrupted {
+                column,
+                found: character,
+                unmatched_opener: unmatched_opener.copied(),
+            }),
+        }
     }
 
-    fn value(&self) -> char {
-        self.0
+    fn reset(&mut self) {
+        self.syntax_stack.clear();
+    }
+
+    /// The pairs left open, innermost first, whose closers complete the line.
+    fn suggest_completion(&self) -> Vec<BracketPair> {
+        self.syntax_stack
+            .iter()
+            .rev()
+            .map(|(_opened_at, open_pair)| *open_pair)
+            .collect()
     }
 }
 
-struct SyntaxWalker {
-    syntax_stack: Vec<SyntaxCharacter>,
+/// Columns count from 1.
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum SyntaxError {
+    /// A closer that does not close the innermost open pair.
+    Corrupted {
+        column: usize,
+        found: char,
+        /// The innermost pair left open, with the column it was opened at, if any.
+        unmatched_opener: Option<(usize, BracketPair)>,
+    },
+    /// A character that is not part of any bracket pair.
+    UnknownCharacter { column: usize, found: char },
 }
 
-impl SyntaxWalker {
-    fn new() -> Self {
-        SyntaxWalker {
-            syntax_stack: Vec::new(),
+impl SyntaxError {
+    fn column(&self) -> usize {
+        match self {
+            SyntaxError::Corrupted { column, .. }
+            | SyntaxError::UnknownCharacter { column, .. } => *column,
         }
     }
 
-    fn walk(&mut self, syntax: Vec<SyntaxCharacter>) -> Result<(), SyntaxError> {
-        let syntax_error = syntax
-            .into_iter()
-            .fold_while(
-                None,
-                |_syntax_error: Option<SyntaxError>, character: SyntaxCharacter| {
-                    if character.closes() {
-                        if let Some(previous) = self.syntax_stack.last() {
-                            if previous.is_closed_by(character) {
-                                self.syntax_stack.pop();
-                                return Continue(None);
-                            }
-                        }
-                        Done(Some(SyntaxError::new(character)))
-                    } else {
-                        self.syntax_stack.push(character);
-                        Continue(None)
-                    }
-                },
-            )
-            .into_inner();
-
-        match syntax_error {
-            None => Ok(()),
-            Some(error) => Err(error),
-        }
-    }
-
-    fn suggest_completion(&self) -> Vec<SyntaxCharacter> {
-        self.syntax_stack
-            .iter()
-            .rev()
-            .map(|closer| closer.closing_character())
-            .collect()
+    fn unmatched_opener_column(&self) -> Option<usize> {
+        match self {
+            SyntaxError::Corrupted {
+                unmatched_opener, ..
+            } => unmatched_opener.map(|(opened_at, _open_pair)| opened_at),
+            SyntaxError::UnknownCharacter { .. } => None,
+        }
     }
 }
 
+/// A line that is corrupted or uses an unknown character, with where it goes wrong.
 #[derive(Debug, derive_new::new)]
-struct SyntaxError(SyntaxCharacter);
+pub(crate) struct SyntaxDiagnostic {
+    /// Counting from 1.
+    line_number: usize,
+    line: String,
+    error: SyntaxError,
+}
+
+impl Display for SyntaxDiagnostic {
+    /// Prints the line with a caret under the unexpected character and a dash under the opener
+    /// it should have closed.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let error = &self.error;
+        write!(f, "line {}, column {}: ", self.line_number, error.column())?;
+        match error {
+            SyntaxError::Corrupted {
+                found,
+                unmatched_opener: Some((_opened_at, open_pair)),
+                ..
+            } => writeln!(f, "expected {}, found {}", open_pair.closer, found)?,
+            SyntaxError::Corrupted {
+                found,
+                unmatched_opener: None,
+                ..
+            } => writeln!(f, "found {} with nothing open", found)?,
+            SyntaxError::UnknownCharacter { found, .. } => {
+                writeln!(f, "unknown character {}", found)?
+            }
+        }
+        writeln!(f, "{}", self.line)?;
+
+        let markers: String = (1..=error.column())
+            .map(|column| {
+                if column == error.column() {
+                    '^'
+                } else if Some(column) == error.unmatched_opener_column() {
+                    '-'
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        write!(f, "{}", markers)
+    }
+}
 
 #[derive(derive_new::new)]
-struct SyntaxErrorScorer;
+struct SyntaxErrorScorer<'a> {
+    configuration: &'a SyntaxConfiguration,
+}
 
-impl SyntaxErrorScorer {
+impl SyntaxErrorScorer<'_> {
+    /// Only corrupted lines score, not those with unknown characters.
     fn score(&self, error: &SyntaxError) -> u64 {
-        match error.0.value() {
-            ')' => 3,
-            ']' => 57,
-            '}' => 1197,
-            '>' => 25137,
-            _ => unimplemented!(),
+        match error {
+            SyntaxError::Corrupted { found, .. } => self
+                .configuration
+                .closed_by(*found)
+                .map(|pair| pair.error_score)
+                .unwrap_or(0),
+            SyntaxError::UnknownCharacter { .. } => 0,
         }
     }
 }
 
 #[derive(derive_new::new)]
-struct SyntaxCompletionScorer;
+struct SyntaxCompletionScorer<'a> {
+    configuration: &'a SyntaxConfiguration,
+}
 
-impl SyntaxCompletionScorer {
-    fn score(&self, characters: Vec<SyntaxCharacter>) -> u64 {
-        characters.into_iter().fold(0_u64, |score, character| {
-            score * 5 + Self::character_score(character)
+impl SyntaxCompletionScorer<'_> {
+    /// `None` if the score is too large to count.
+    fn score(&self, completion: Vec<BracketPair>) -> Option<u64> {
+        completion.into_iter().try_fold(0_u64, |score, pair| {
+            score
+                .checked_mul(self.configuration.completion_multiplier)?
+                .checked_add(pair.completion_score)
         })
     }
+}
+
+/// Splits bytes into lines as `str::lines` does, ignoring empty lines.
+struct StreamingSyntaxChecker<'a> {
+    walker: SyntaxWalker<'a>,
+    error_scorer: SyntaxErrorScorer<'a>,
+    completion_scorer: SyntaxCompletionScorer<'a>,
+    /// Counting from 1, including empty lines.
+    line_number: usize,
+    /// Number of characters read on the current line.
+    column: usize,
+    line_error: Option<SyntaxError>,
+    /// Whether the last byte was a carriage return, dropped if a line feed follows.
+    carriage_return: bool,
+    syntax_error_score: u64,
+    completion_scores: Vec<u64>,
+}
+
+impl<'a> StreamingSyntaxChecker<'a> {
+    fn new(configuration: &'a SyntaxConfiguration) -> Self {
+        StreamingSyntaxChecker {
+            walker: SyntaxWalker::new(configuration),
+            error_scorer: SyntaxErrorScorer::new(configuration),
+            completion_scorer: SyntaxCompletionScorer::new(configuration),
+            line_number: 1,
+            column: 0,
+            line_error: None,
+            carriage_return: false,
+            syntax_error_score: 0,
+            completion_scores: Vec::new(),
+        }
+    }
+
+    fn feed(&mut self, byte: u8) -> Result<(), CompletionScoreOverflowError> {
+        if std::mem::take(&mut self.carriage_return) {
+            if byte == b'\n' {
+                return self.end_line();
+            }
+            self.step('\r');
+        }
+        match byte {
+            b'\n' => return self.end_line(),
+            b'\r' => self.carriage_return = true,
+            byte if byte.is_ascii() => self.step(byte as char),
+            _ => self.step(char::REPLACEMENT_CHARACTER),
+        }
+        Ok(())
+    }
+
+    fn step(&mut self, character: char) {
+        self.column += 1;
+        if self.line_error.is_none() {
+            self.line_error = self.walker.step(character, self.column).err();
+        }
+    }
+
+    fn end_line(&mut self) -> Result<(), CompletionScoreOverflowError> {
+        if self.column > 0 {
+            match self.line_error.take() {
+                Some(error) => self.syntax_error_score += self.error_scorer.score(&error),
+                None => self.completion_scores.push(
+                    self.completion_scorer
+                        .score(self.walker.suggest_completion())
+                        .ok_or_else(|| CompletionScoreOverflowError::new(self.line_number))?,
+                ),
+            }
+        }
+        self.walker.reset();
+        self.line_number += 1;
+        self.column = 0;
+        Ok(())
+    }
 
-    fn character_score(character: SyntaxCharacter) -> u64 {
-        match character.value() {
-            ')' => 1,
-            ']' => 2,
-            '}' => 3,
-            '>' => 4,
-            _ => unimplemented!(),
+    fn finish(mut self) -> Result<SyntaxTotals, CompletionScoreOverflowError> {
+        if std::mem::take(&mut self.carriage_return) {
+            self.step('\r');
         }
+        self.end_line()?;
+
+        let middle = self.completion_scores.len() / 2;
+        let middle_completion_score = (!self.completion_scores.is_empty())
+            .then(|| *self.completion_scores.select_nth_unstable(middle).1);
+        Ok(SyntaxTotals::new(
+            self.syntax_error_score,
+            middle_completion_score,
+        ))
     }
 }
 
@@ -178,6 +518,8 @@ impl SyntaxCompletionScorer {
 mod tests {
     use speculoos::prelude::*;
 
+    use crate::domain::solution_executor::test_random::TestRandom;
+
     use super::*;
 
     fn test_data() -> Vec<String> {
@@ -205,6 +547,194 @@ mod tests {
 
     #[test]
     fn calculates_completion_score() {
-        assert_that(&Day10SolutionExecutor::new().part_2(test_data())).is_equal_to(288957)
+        assert_that(&Day10SolutionExecutor::new().part_2(test_data())).is_equal_to(Ok(288957))
+    }
+
+    #[test]
+    fn locates_syntax_errors() {
+        let diagnostics = Day10SolutionExecutor::new().diagnostics(test_data());
+        let locations: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| match &diagnostic.error {
+                SyntaxError::Corrupted {
+                    column,
+                    found,
+                    unmatched_opener: Some((opened_at, open_pair)),
+                } => (
+                    diagnostic.line_number,
+                    *column,
+                    open_pair.closer,
+                    *found,
+                    *opened_at,
+                ),
+                error => panic!("unexpected syntax error {:?}", error),
+            })
+            .collect();
+
+        assert_that(&locations).is_equal_to(vec![
+            (3, 13, ']', '}', 8),
+            (5, 9, ']', ')', 4),
+            (6, 8, ')', ']', 5),
+            (8, 11, '>', ')', 8),
+            (9, 17, ']', '>', 6),
+        ]);
+    }
+
+    #[test]
+    fn points_to_syntax_errors() {
+        let diagnostics = Day10SolutionExecutor::new().diagnostics(test_data());
+        assert_that(&diagnostics[0].to_string()).is_equal_to(
+            "line 3, column 13: expected ], found }\n{([(<{}[<>[]}>{[]{[(<()>\n       -    ^"
+                .to_string(),
+        );
+    }
+
+    #[test]
+    fn reports_closer_with_nothing_open() {
+        let diagnostics = Day10SolutionExecutor::new().diagnostics(vec!["()]".to_string()]);
+        assert_that(&diagnostics[0].to_string())
+            .is_equal_to("line 1, column 3: found ] with nothing open\n()]\n  ^".to_string());
+    }
+
+    #[test]
+    fn reports_unknown_characters() {
+        let executor = Day10SolutionExecutor::new();
+        let input = vec!["(x)".to_string(), "(]".to_string()];
+
+        assert_that(&executor.diagnostics(input.clone())[0].to_string())
+            .is_equal_to("line 1, column 2: unknown character x\n(x)\n ^".to_string());
+        assert_that(&executor.part_1(input)).is_equal_to(57);
+    }
+
+    #[test]
+    fn scores_configured_bracket_pairs() {
+        let executor = Day10SolutionExecutor::with_configuration(
+            SyntaxConfiguration::with_pairs(vec![
+                BracketPair::new('(', ')', 3, 1),
+                BracketPair::new('a', 'b', 10, 2),
+            ])
+            .unwrap(),
+        );
+
+        assert_that(&executor.part_1(vec!["(a)".to_string(), "ab(".to_string()])).is_equal_to(3);
+        assert_that(&executor.part_2(vec!["(a".to_string()])).is_equal_to(Ok(2 * 5 + 1));
+    }
+
+    #[test]
+    fn rejects_brackets_repeated_across_pairs() {
+        assert_that(&SyntaxConfiguration::with_pairs(vec![
+            BracketPair::new('(', ')', 3, 1),
+            BracketPair::new('(', ']', 57, 2),
+        ]))
+        .is_err_containing(RepeatedBracketError::new('('));
+        assert_that(&SyntaxConfiguration::with_pairs(vec![
+            BracketPair::new('a', 'b', 1, 1),
+            BracketPair::new('b', 'a', 2, 2),
+        ]))
+        .is_err_containing(RepeatedBracketError::new('b'));
+    }
+
+    #[test]
+    fn scores_both_parts_from_a_stream() {
+        let input = test_data().join("\r\n\n");
+        let reader = std::io::BufReader::with_capacity(3, input.as_bytes());
+
+        assert_that(&Day10SolutionExecutor::new().check_stream(reader).unwrap())
+            .is_equal_to(SyntaxTotals::new(26397, Some(288957)));
+    }
+
+    #[test]
+    fn streams_input_without_incomplete_lines() {
+        let totals = Day10SolutionExecutor::new()
+            .check_stream("(]\n<é>\n".as_bytes())
+            .unwrap();
+
+        assert_that(&totals).is_equal_to(SyntaxTotals::new(57, None));
+    }
+
+    #[test]
+    fn repairs_incomplete_lines() {
+        let repaired: Vec<String> = Day10SolutionExecutor::new()
+            .repair(test_data())
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_that(&repaired[0]).is_equal_to("[({(<(())[]>[[{[]{<()<>>}}]])})]".to_string());
+        assert_that(&repaired[2])
+            .is_equal_to("# corrupted at column 13: {([(<{}[<>[]}>{[]{[(<()>".to_string());
+    }
+
+    #[test]
+    fn flags_lines_with_unknown_characters() {
+        let repaired: Vec<String> = Day10SolutionExecutor::new()
+            .repair(vec!["(x".to_string()])
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_that(&repaired)
+            .is_equal_to(vec!["# unknown character x at column 2: (x".to_string()]);
+    }
+
+    #[test]
+    fn repaired_lines_are_complete() {
+        let configuration = SyntaxConfiguration::default();
+        let syntax_checker = SyntaxChecker::new(&configuration);
+        let repaired_lines: Vec<String> = Day10SolutionExecutor::new()
+            .repair(test_data())
+            .into_iter()
+            .flat_map(|repaired_line| match repaired_line {
+                RepairedLine::Repaired(line) => Some(line),
+                RepairedLine::Unrepairable { .. } => None,
+            })
+            .collect();
+
+        assert_that(&repaired_lines).has_length(5);
+        repaired_lines.iter().for_each(|line| {
+            assert_that(&syntax_checker.check_line(line)).is_ok();
+            assert_that(&syntax_checker.complete_incomplete_line(line)).is_empty();
+        });
+    }
+
+    #[test]
+    fn streamed_totals_match_whole_input_totals() {
+        let executor = Day10SolutionExecutor::new();
+        let mut random = TestRandom::new(10);
+        let openers = ['(', '[', '{', '<'];
+        let closers = [')', ']', '}', '>'];
+        // mostly openers and short enough that no completion score overflows
+        let input: Vec<String> = (0..301)
+            .map(|_line| {
+                (0..1 + random.below(25))
+                    .map(|_character| match random.below(6) {
+                        0 => closers[random.below(4) as usize],
+                        _ => openers[random.below(4) as usize],
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let totals = executor.check_stream(input.join("\n").as_bytes()).unwrap();
+
+        assert_that(&totals).is_equal_to(SyntaxTotals::new(
+            executor.part_1(input.clone()),
+            executor.part_2(input).ok(),
+        ));
+    }
+
+    #[test]
+    fn reports_completion_scores_too_large_to_count() {
+        let executor = Day10SolutionExecutor::new();
+        let input = vec!["((".to_string(), "<".repeat(40)];
+
+        assert_that(&executor.part_2(input.clone()))
+            .is_equal_to(Err(CompletionScoreOverflowError::new(2)));
+        assert_that(&matches!(
+            executor.check_stream(input.join("\n").as_bytes()),
+            Err(StreamCheckError::CompletionScoreOverflow(error))
+                if error == CompletionScoreOverflowError::new(2)
+        ))
+        .is_true();
     }
 }
@@ -1,17 +1,76 @@
+use std::fmt::{Display, Formatter};
+
+use crate::domain::grid::{Adjacency, Grid, GridPosition};
 use crate::domain::solution_executor::SolutionExecutor;
 
+#[derive(Debug, Copy, Clone, derive_new::new, derive_getters::Getters)]
+pub(crate) struct SimulationParameters {
+    /// Number of steps simulated for part 1.
+    steps: usize,
+    /// An octopus flashes once its energy level exceeds this.
+    flash_threshold: u8,
+    /// Which neighbours a flash raises the energy of.
+    adjacency: Adjacency,
+}
+
+impl Default for SimulationParameters {
+    fn default() -> Self {
+        SimulationParameters::new(100, 9, Adjacency::EightWay)
+    }
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new, Eq, PartialEq)]
+#[error("the octopuses never all flash at once, after step {step} they repeat an earlier step")]
+pub(crate) struct NeverSynchronisedError {
+    step: usize,
+}
+
 #[derive(derive_new::new)]
-pub(crate) struct Day11SolutionExecutor;
+pub(crate) struct Day11SolutionExecutor {
+    #[new(default)]
+    parameters: SimulationParameters,
+}
+
+impl Day11SolutionExecutor {
+    pub(crate) fn with_parameters(parameters: SimulationParameters) -> Self {
+        Day11SolutionExecutor { parameters }
+    }
+
+    /// Records the state of the map before any steps and after each of the first `steps` steps.
+    pub(crate) fn trace(&self, input: Grid<u8>, steps: usize) -> Vec<StepTrace> {
+        let mut energy_map = self.to_octopus_map(input);
+        let mut traces = vec![StepTrace::new(0, Vec::new(), energy_map.energy_levels())];
+
+        (1..=steps).for_each(|step| {
+            let flash_order = energy_map.increment_energy_levels();
+            energy_map.reset_flashing();
+            traces.push(StepTrace::new(
+                step,
+                flash_order,
+                energy_map.energy_levels(),
+            ));
+        });
+        traces
+    }
+
+    fn to_octopus_map(&self, map: Grid<u8>) -> OctopusEnergyMap {
+        OctopusEnergyMap::new(
+            map.map(Octopus::new),
+            self.parameters.flash_threshold,
+            self.parameters.adjacency,
+        )
+    }
+}
 
 impl SolutionExecutor for Day11SolutionExecutor {
-    type Input = Vec<Vec<u8>>;
+    type Input = Grid<u8>;
     type Part1Output = usize;
-    type Part2Output = usize;
+    type Part2Output = Result<usize, NeverSynchronisedError>;
 
     fn part_1(&self, input: Self::Input) -> Self::Part1Output {
-        let mut energy_map = to_octopus_map(input);
+        let mut energy_map = self.to_octopus_map(input);
         let mut total_flashes = 0;
-        (0..100).for_each(|_i| {
+        (0..self.parameters.steps).for_each(|_i| {
             energy_map.increment_energy_levels();
             total_flashes += energy_map.count_flashing();
             energy_map.reset_flashing();
@@ -20,152 +79,151 @@ impl SolutionExecutor for Day11SolutionExecutor {
     }
 
     fn part_2(&self, input: Self::Input) -> Self::Part2Output {
-        let mut energy_map = to_octopus_map(input);
-        let mut step_count = 0;
-        while energy_map.count_flashing() != 100 {
-            energy_map.reset_flashing();
-            step_count += 1;
-            energy_map.increment_energy_levels();
+        let mut energy_map = self.to_octopus_map(input);
+        if energy_map.all_flashing() {
+            return Ok(0);
         }
-        step_count
-    }
-}
-
-#[derive(derive_new::new, Copy, Clone, Debug)]
-struct MapPosition {
-    row: usize,
-    col: usize,
-}
-
-impl MapPosition {
-    pub(crate) fn adjacent(&self) -> Vec<MapPosition> {
-        vec![
-            self.above(),
-            self.above().and_then(|pos| pos.left()),
-            self.above().and_then(|pos| pos.right()),
-            self.left(),
-            self.right(),
-            self.below(),
-            self.below().and_then(|pos| pos.left()),
-            self.below().and_then(|pos| pos.right()),
-        ]
-        .into_iter()
-        .flatten()
-        .filter(MapPosition::is_valid)
-        .collect()
-    }
-
-    fn above(&self) -> Option<MapPosition> {
-        self.row
-            .checked_add(1)
-            .map(|row| MapPosition::new(row, self.col))
-    }
-
-    fn below(&self) -> Option<MapPosition> {
-        self.row
-            .checked_sub(1)
-            .map(|row| MapPosition::new(row, self.col))
-    }
-
-    fn left(&self) -> Option<MapPosition> {
-        self.col
-            .checked_sub(1)
-            .map(|col| MapPosition::new(self.row, col))
-    }
 
-    fn right(&self) -> Option<MapPosition> {
-        self.col
-            .checked_add(1)
-            .map(|col| MapPosition::new(self.row, col))
-    }
+        // the map has finitely many states, so it either synchronises or repeats one; Brent's
+        // cycle detection finds a repeat while only keeping the state at the last power of two
+        let mut saved_energy_levels = energy_map.energy_levels();
+        let mut next_save = 1;
+        for step in 1.. {
+            energy_map.increment_energy_levels();
+            if energy_map.all_flashing() {
+                return Ok(step);
+            }
+            energy_map.reset_flashing();
 
-    fn is_valid(&self) -> bool {
-        self.row < 10 && self.col < 10
+            let energy_levels = energy_map.energy_levels();
+            if energy_levels == saved_energy_levels {
+                return Err(NeverSynchronisedError::new(step));
+            }
+            if step == next_save {
+                saved_energy_levels = energy_levels;
+                next_save *= 2;
+            }
+        }
+        unreachable!("steps are unbounded")
     }
 }
 
 #[derive(Debug, derive_new::new)]
 struct OctopusEnergyMap {
-    inner: Vec<Vec<Octopus>>,
+    inner: Grid<Octopus>,
+    flash_threshold: u8,
+    adjacency: Adjacency,
 }
 
 impl OctopusEnergyMap {
-    fn at_position(&mut self, position: MapPosition) -> &mut Octopus {
+    fn at_position(&mut self, position: GridPosition) -> &mut Octopus {
         self.inner
-            .get_mut(position.row)
-            .expect("row does not exist")
-            .get_mut(position.col)
-            .expect("col does not exist")
-    }
-
-    fn all_positions() -> Vec<MapPosition> {
-        (0..10)
-            .flat_map(|row| {
-                (0..10)
-                    .map(|col| MapPosition::new(row, col))
-                    .collect::<Vec<MapPosition>>()
-            })
-            .collect()
+            .get_mut(position)
+            .expect("position does not exist")
     }
 
-    fn increment_energy_levels(&mut self) {
-        let mut to_be_raised: Vec<MapPosition> = Self::all_positions();
+    /// Returns the positions of the octopuses that flashed, in the order they flashed.
+    fn increment_energy_levels(&mut self) -> Vec<GridPosition> {
+        let mut to_be_raised: Vec<GridPosition> = self.inner.positions().collect();
+        let mut flash_order: Vec<GridPosition> = Vec::new();
 
         while let Some(position) = to_be_raised.pop() {
+            let flash_threshold = self.flash_threshold;
             let octopus = self.at_position(position);
-            let previously_flashing = octopus.flashing();
             octopus.raise_energy();
 
-            if !previously_flashing && octopus.flashing() {
-                to_be_raised.append(&mut position.adjacent());
+            if octopus.try_flash(flash_threshold) {
+                flash_order.push(position);
+                to_be_raised.extend(self.inner.neighbours(position, self.adjacency));
             }
         }
+        flash_order
     }
 
     fn reset_flashing(&mut self) {
-        self.inner.iter_mut().for_each(|row| {
-            row.iter_mut()
-                .for_each(|octopus| octopus.reset_if_flashing())
-        })
+        self.inner
+            .values_mut()
+            .for_each(|octopus| octopus.reset_if_flashing())
     }
 
     fn count_flashing(&self) -> usize {
         self.inner
-            .iter()
-            .flat_map(|row| row.iter().filter(|octopus| octopus.flashing()))
+            .values()
+            .filter(|octopus| octopus.flashing())
             .count()
     }
+
+    fn all_flashing(&self) -> bool {
+        self.inner.values().all(Octopus::flashing)
+    }
+
+    fn energy_levels(&self) -> Grid<u8> {
+        self.inner.clone().map(|octopus| octopus.energy_level)
+    }
 }
 
-#[derive(Debug, derive_new::new)]
+#[derive(Debug, Clone, derive_new::new)]
 struct Octopus {
     energy_level: u8,
+    #[new(default)]
+    flashing: bool,
 }
 
 impl Octopus {
     fn flashing(&self) -> bool {
-        self.energy_level > 9
+        self.flashing
     }
 
     fn raise_energy(&mut self) {
-        if self.energy_level <= 9 {
-            self.energy_level += 1;
+        self.energy_level = self.energy_level.saturating_add(1);
+    }
+
+    /// Flashes if the energy level exceeds the threshold and the octopus has not already flashed.
+    fn try_flash(&mut self, flash_threshold: u8) -> bool {
+        if !self.flashing && self.energy_level > flash_threshold {
+            self.flashing = true;
+            return true;
         }
+        false
     }
 
     fn reset_if_flashing(&mut self) {
-        if self.flashing() {
+        if self.flashing {
             self.energy_level = 0;
+            self.flashing = false;
         }
     }
 }
 
-fn to_octopus_map(map: Vec<Vec<u8>>) -> OctopusEnergyMap {
-    OctopusEnergyMap::new(
-        map.into_iter()
-            .map(|row| row.into_iter().map(Octopus::new).collect())
-            .collect(),
-    )
+#[derive(Debug, derive_new::new, derive_getters::Getters)]
+pub(crate) struct StepTrace {
+    step: usize,
+    flash_order: Vec<GridPosition>,
+    energy_levels: Grid<u8>,
+}
+
+impl StepTrace {
+    /// Renders the map as the puzzle text does, passing flashed cells through `highlight`.
+    pub(crate) fn render(&self, highlight: impl Fn(String) -> String) -> String {
+        let heading = match self.step {
+            0 => "Before any steps:".to_string(),
+            step => format!("After step {}:", step),
+        };
+        let energy_levels = self.energy_levels.display_with(|position, energy_level| {
+            if self.flash_order.contains(&position) {
+                highlight(energy_level.to_string())
+            } else {
+                energy_level.to_string()
+            }
+        });
+        format!("{}\n{}", heading, energy_levels)
+    }
+}
+
+impl Display for StepTrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|energy_level| energy_level))
+    }
 }
 
 #[cfg(test)]
@@ -174,8 +232,8 @@ mod tests {
 
     use super::*;
 
-    fn test_data() -> Vec<Vec<u8>> {
-        vec![
+    fn test_data() -> Grid<u8> {
+        Grid::new(vec![
             vec![5, 4, 8, 3, 1, 4, 3, 2, 2, 3],
             vec![2, 7, 4, 5, 8, 5, 4, 7, 1, 1],
             vec![5, 2, 6, 4, 5, 5, 6, 1, 7, 3],
@@ -186,7 +244,8 @@ mod tests {
             vec![6, 8, 8, 2, 8, 8, 1, 1, 3, 4],
             vec![4, 8, 4, 6, 8, 4, 8, 5, 5, 4],
             vec![5, 2, 8, 3, 7, 5, 1, 5, 2, 6],
-        ]
+        ])
+        .unwrap()
     }
 
     #[test]
@@ -196,6 +255,106 @@ mod tests {
 
     #[test]
     fn counts_steps_before_synchronised_flashing() {
-        assert_that(&Day11SolutionExecutor::new().part_2(test_data())).is_equal_to(195);
+        assert_that(&Day11SolutionExecutor::new().part_2(test_data())).is_equal_to(Ok(195));
+    }
+
+    #[test]
+    fn traces_flash_order_for_each_step() {
+        let traces = Day11SolutionExecutor::new().trace(test_data(), 10);
+        let flash_counts: Vec<usize> = traces
+            .iter()
+            .map(|trace| trace.flash_order().len())
+            .collect();
+
+        assert_that(&flash_counts.first()).is_equal_to(Some(&0));
+        assert_that(&flash_counts.get(1)).is_equal_to(Some(&0));
+        assert_that(&flash_counts.get(2)).is_equal_to(Some(&35));
+        assert_that(&flash_counts.iter().sum::<usize>()).is_equal_to(204);
+    }
+
+    #[test]
+    fn renders_steps_in_puzzle_format() {
+        let input = Grid::new(vec![
+            vec![1, 1, 1, 1, 1],
+            vec![1, 9, 9, 9, 1],
+            vec![1, 9, 1, 9, 1],
+            vec![1, 9, 9, 9, 1],
+            vec![1, 1, 1, 1, 1],
+        ])
+        .unwrap();
+        let rendered: Vec<String> = Day11SolutionExecutor::new()
+            .trace(input, 2)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_that(&rendered).is_equal_to(vec![
+            "Before any steps:\n11111\n19991\n19191\n19991\n11111".to_string(),
+            "After step 1:\n34543\n40004\n50005\n40004\n34543".to_string(),
+            "After step 2:\n45654\n51115\n61116\n51115\n45654".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn highlights_flashed_cells() {
+        let input = Grid::new(vec![vec![9, 1]]).unwrap();
+        let trace = Day11SolutionExecutor::new().trace(input, 1).pop().unwrap();
+
+        assert_that(&trace.render(|energy_level| format!("*{}*", energy_level)))
+            .is_equal_to("After step 1:\n*0*3".to_string());
+    }
+
+    #[test]
+    fn counts_flashes_with_custom_step_count() {
+        let parameters = SimulationParameters::new(10, 9, Adjacency::EightWay);
+        assert_that(&Day11SolutionExecutor::with_parameters(parameters).part_1(test_data()))
+            .is_equal_to(204);
+    }
+
+    #[test]
+    fn flashes_above_custom_threshold() {
+        let input = Grid::new(vec![vec![5, 3]]).unwrap();
+        let parameters = SimulationParameters::new(1, 5, Adjacency::EightWay);
+        let trace = Day11SolutionExecutor::with_parameters(parameters)
+            .trace(input, 1)
+            .pop()
+            .unwrap();
+
+        assert_that(&trace.to_string()).is_equal_to("After step 1:\n05".to_string());
+    }
+
+    #[test]
+    fn raises_only_four_way_neighbours_of_flashes() {
+        let input = Grid::new(vec![vec![9, 1], vec![1, 1]]).unwrap();
+        let parameters = SimulationParameters::new(1, 9, Adjacency::FourWay);
+        let trace = Day11SolutionExecutor::with_parameters(parameters)
+            .trace(input, 1)
+            .pop()
+            .unwrap();
+
+        assert_that(&trace.to_string()).is_equal_to("After step 1:\n03\n32".to_string());
+    }
+
+    fn non_square_test_data() -> Grid<u8> {
+        Grid::new(vec![vec![9, 9, 9], vec![9, 9, 9]]).unwrap()
+    }
+
+    #[test]
+    fn counts_flashes_in_100_steps_on_non_square_map() {
+        assert_that(&Day11SolutionExecutor::new().part_1(non_square_test_data())).is_equal_to(60);
+    }
+
+    #[test]
+    fn counts_steps_before_synchronised_flashing_on_non_square_map() {
+        assert_that(&Day11SolutionExecutor::new().part_2(non_square_test_data()))
+            .is_equal_to(Ok(1));
+    }
+
+    #[test]
+    fn reports_octopuses_that_never_synchronise() {
+        let parameters = SimulationParameters::new(100, 9, Adjacency::FourWay);
+        let result = Day11SolutionExecutor::with_parameters(parameters).part_2(test_data());
+
+        assert_that(&result).is_err();
     }
 }
@@ -11,6 +11,8 @@ pub(crate) mod day_10;
 pub(crate) mod day_11;
 pub(crate) mod day_12;
 pub(crate) mod day_13;
+#[cfg(test)]
+pub(crate) mod test_random;
 
 pub(crate) trait SolutionExecutor {
     type Input;
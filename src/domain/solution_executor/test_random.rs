@@ -0,0 +1,19 @@
+/// A linear congruential generator, so generated test inputs are the same on every run.
+pub(crate) struct TestRandom {
+    state: u64,
+}
+
+impl TestRandom {
+    pub(crate) fn new(seed: u64) -> Self {
+        TestRandom { state: seed }
+    }
+
+    /// A value in `0..bound`.
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.state >> 33) % bound
+    }
+}
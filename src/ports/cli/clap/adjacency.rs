@@ -0,0 +1,34 @@
+use std::str::FromStr;
+
+use crate::domain::grid as domain;
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum Adjacency {
+    FourWay,
+    EightWay,
+}
+
+impl FromStr for Adjacency {
+    type Err = ParseAdjacencyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "4" | "four-way" => Ok(Adjacency::FourWay),
+            "8" | "eight-way" => Ok(Adjacency::EightWay),
+            _ => Err(ParseAdjacencyError::new(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new)]
+#[error("could not parse adjacency {0}, expected 4 or 8")]
+pub(crate) struct ParseAdjacencyError(String);
+
+impl From<Adjacency> for domain::Adjacency {
+    fn from(from: Adjacency) -> Self {
+        match from {
+            Adjacency::FourWay => domain::Adjacency::FourWay,
+            Adjacency::EightWay => domain::Adjacency::EightWay,
+        }
+    }
+}
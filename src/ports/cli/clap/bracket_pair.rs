@@ -0,0 +1,113 @@
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use crate::domain::solution_executor::day_10 as domain;
+use crate::ports::cli::clap::inputs::comma_separated_list::CommaSeparatedList;
+
+/// Written as the opener and closer followed by the error and completion scores, e.g. `():3:1`.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct BracketPair {
+    opener: char,
+    closer: char,
+    error_score: u64,
+    completion_score: u64,
+}
+
+impl BracketPair {
+    pub(crate) fn is_ascii(&self) -> bool {
+        self.opener.is_ascii() && self.closer.is_ascii()
+    }
+}
+
+impl FromStr for BracketPair {
+    type Err = ParseBracketPairError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseBracketPairError::new(s.to_string());
+        let mut parts = s.split(':');
+        let mut brackets = parts.next().ok_or_else(error)?.chars();
+        let (opener, closer) = match (brackets.next(), brackets.next(), brackets.next()) {
+            (Some(opener), Some(closer), None) if opener != closer => (opener, closer),
+            _ => return Err(error()),
+        };
+        let mut score = || {
+            parts
+                .next()
+                .and_then(|score| score.parse().ok())
+                .ok_or_else(error)
+        };
+        let error_score = score()?;
+        let completion_score = score()?;
+        if parts.next().is_some() {
+            return Err(error());
+        }
+
+        Ok(BracketPair {
+            opener,
+            closer,
+            error_score,
+            completion_score,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new)]
+#[error("could not parse bracket pair {0}, expected an opener and closer followed by error and completion scores (e.g. ():3:1)")]
+pub(crate) struct ParseBracketPairError(String);
+
+impl From<BracketPair> for domain::BracketPair {
+    fn from(from: BracketPair) -> Self {
+        domain::BracketPair::new(
+            from.opener,
+            from.closer,
+            from.error_score,
+            from.completion_score,
+        )
+    }
+}
+
+/// Comma separated bracket pairs, none of which share a character.
+#[derive(Debug, Clone)]
+pub(crate) struct BracketPairs {
+    pairs: Vec<BracketPair>,
+}
+
+impl BracketPairs {
+    pub(crate) fn is_ascii(&self) -> bool {
+        self.pairs.iter().all(BracketPair::is_ascii)
+    }
+}
+
+impl FromStr for BracketPairs {
+    type Err = ParseBracketPairsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pairs = s.parse::<CommaSeparatedList<BracketPair>>()?.inner();
+        if let Some(character) = pairs
+            .iter()
+            .flat_map(|pair| [pair.opener, pair.closer])
+            .duplicates()
+            .next()
+        {
+            return Err(ParseBracketPairsError::RepeatedCharacter(character));
+        }
+
+        Ok(BracketPairs { pairs })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ParseBracketPairsError {
+    #[error(transparent)]
+    Pair(#[from] ParseBracketPairError),
+    #[error("bracket {0} appears in more than one pair")]
+    RepeatedCharacter(char),
+}
+
+impl From<BracketPairs> for domain::SyntaxConfiguration {
+    fn from(from: BracketPairs) -> Self {
+        domain::SyntaxConfiguration::with_pairs(from.pairs.into_iter().map(Into::into).collect())
+            .expect("bracket pairs were not validated")
+    }
+}
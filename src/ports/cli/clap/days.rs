@@ -1,16 +1,22 @@
+use std::fs::File;
+use std::io::{stdout, BufReader, IsTerminal};
 use std::path::Path;
 
+use clap::ErrorKind;
+use clap::IntoApp;
+use itertools::Itertools;
+
 use crate::domain::solution_executor::day_01::Day1SolutionExecutor;
 use crate::domain::solution_executor::day_02::Day2SolutionExecutor;
 use crate::domain::solution_executor::day_03::Day3SolutionExecutor;
 use crate::domain::solution_executor::day_04::Day4SolutionExecutor;
-use crate::domain::solution_executor::day_05::Day5SolutionExecutor;
-use crate::domain::solution_executor::day_06::Day6SolutionExecutor;
-use crate::domain::solution_executor::day_07::Day7SolutionExecutor;
+use crate::domain::solution_executor::day_05::{Day5SolutionExecutor, VentMapParameters};
+use crate::domain::solution_executor::day_06::{Day6SolutionExecutor, LanternfishParameters};
+use crate::domain::solution_executor::day_07::{AlignmentParameters, Day7SolutionExecutor};
 use crate::domain::solution_executor::day_08::Day8SolutionExecutor;
-use crate::domain::solution_executor::day_09::Day9SolutionExecutor;
+use crate::domain::solution_executor::day_09::{Day9SolutionExecutor, TerrainParameters};
 use crate::domain::solution_executor::day_10::Day10SolutionExecutor;
-use crate::domain::solution_executor::day_11::Day11SolutionExecutor;
+use crate::domain::solution_executor::day_11::{Day11SolutionExecutor, SimulationParameters};
 use crate::domain::solution_executor::day_12::Day12SolutionExecutor;
 use crate::domain::solution_executor::day_13::Day13SolutionExecutor;
 use crate::domain::solution_executor::SolutionExecutor;
@@ -19,11 +25,19 @@ use crate::ports::cli::clap::inputs::bingo_game::BingoGame;
 use crate::ports::cli::clap::inputs::cave_connection::CaveConnection;
 use crate::ports::cli::clap::inputs::comma_separated_list::CommaSeparatedList;
 use crate::ports::cli::clap::inputs::direction_and_size::DirectionAndSize;
+use crate::ports::cli::clap::inputs::grid::Grid;
 use crate::ports::cli::clap::inputs::lines::Lines;
 use crate::ports::cli::clap::inputs::origami_instructions::OrigamiInstructions;
 use crate::ports::cli::clap::inputs::straight_line::StraightLine;
 use crate::ports::cli::clap::inputs::submarine_display_signals::SubmarineDisplaySignal;
-use crate::ports::cli::clap::{read_digit_lines, read_input, read_input_str};
+use crate::ports::cli::clap::opts::{
+    Day10Opt, Day11Opt, Day4Opt, Day5Opt, Day6Opt, Day7Opt, Day9Opt, Opt,
+};
+use crate::ports::cli::clap::outputs::csv::write_csv;
+use crate::ports::cli::clap::outputs::heatmap::write_heatmap;
+use crate::ports::cli::clap::overlap_engine::OverlapEngine;
+use crate::ports::cli::clap::rasterisation::Rasterisation;
+use crate::ports::cli::clap::{read_input, read_input_str};
 
 pub(crate) fn run_day_01(part: DayPart, input_path: &Path) -> String {
     let executor = Day1SolutionExecutor::new();
@@ -57,46 +71,192 @@ pub(crate) fn run_day_03(part: DayPart, input_path: &Path) -> String {
     }
 }
 
-pub(crate) fn run_day_04(part: DayPart, input_path: &Path) -> String {
-    let executor = Day4SolutionExecutor::new();
+pub(crate) fn run_day_04(part: DayPart, input_path: &Path, options: &Day4Opt) -> String {
+    let executor = match options.win_rules() {
+        None => Day4SolutionExecutor::new(),
+        Some(win_rules) => Day4SolutionExecutor::with_win_rules(
+            win_rules
+                .clone()
+                .inner()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        ),
+    };
     let input: BingoGame = read_input(input_path).unwrap();
-    match part {
-        DayPart::One => executor.part_1(input.into()).to_string(),
-        DayPart::Two => executor.part_2(input.into()).to_string(),
+    let input: crate::domain::solution_executor::day_04::BingoGame = input.into();
+    let answer = match part {
+        DayPart::One => executor.part_1(input.clone()).to_string(),
+        DayPart::Two => executor.part_2(input.clone()).to_string(),
+    };
+
+    if *options.leaderboard() {
+        executor
+            .timeline(input)
+            .iter()
+            .map(ToString::to_string)
+            .chain(std::iter::once(format!("\n{}", answer)))
+            .join("\n")
+    } else {
+        answer
     }
 }
 
-pub(crate) fn run_day_05(part: DayPart, input_path: &Path) -> String {
-    let executor = Day5SolutionExecutor::new();
+pub(crate) fn run_day_05(part: DayPart, input_path: &Path, options: &Day5Opt) -> String {
+    let executor = Day5SolutionExecutor::with_parameters(day_05_parameters(options));
     let input: Lines<StraightLine> = read_input(input_path).unwrap();
     let domain_input: Vec<crate::domain::solution_executor::day_05::StraightLine> =
         input.inner().into_iter().map(Into::into).collect();
-    match part {
+
+    if let Some(heatmap_path) = options.heatmap() {
+        let overlap_field = exit_on_value_error(
+            executor.overlap_field(domain_input.clone(), *options.heatmap_axis_aligned()),
+        );
+        write_heatmap(heatmap_path, &overlap_field).unwrap();
+    }
+
+    let report = options
+        .overlap_report()
+        .then(|| executor.overlap_report(domain_input.clone(), matches!(part, DayPart::One)));
+    let answer = match part {
         DayPart::One => executor.part_1(domain_input).to_string(),
         DayPart::Two => executor.part_2(domain_input).to_string(),
+    };
+
+    match report {
+        Some(report) => format!("{}\n\n{}", report, answer),
+        None => answer,
     }
 }
 
-pub(crate) fn run_day_06(part: DayPart, input_path: &Path) -> String {
-    let executor = Day6SolutionExecutor::new();
+fn day_05_parameters(options: &Day5Opt) -> VentMapParameters {
+    if *options.overlap_engine() == Some(OverlapEngine::Analytic)
+        && *options.rasterisation() == Some(Rasterisation::Bresenham)
+    {
+        Opt::into_app()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "the analytic overlap engine only supports lattice rasterisation",
+            )
+            .exit()
+    }
+
+    let defaults = VentMapParameters::default();
+    VentMapParameters::new(
+        options
+            .rasterisation()
+            .map(Into::into)
+            .unwrap_or(*defaults.rasterisation()),
+        options
+            .overlap_engine()
+            .map(Into::into)
+            .unwrap_or(*defaults.engine()),
+        options
+            .overlap_threshold()
+            .unwrap_or(*defaults.overlap_threshold()),
+    )
+}
+
+pub(crate) fn run_day_06(part: DayPart, input_path: &Path, options: &Day6Opt) -> String {
+    let parameters = day_06_parameters(options);
+    let executor = Day6SolutionExecutor::with_parameters(parameters);
     let lines: Lines<CommaSeparatedList<u8>> = read_input_str(input_path).unwrap();
-    let input = lines.inner().pop().unwrap();
-    match part {
-        DayPart::One => executor.part_1(input.inner()).to_string(),
-        DayPart::Two => executor.part_2(input.inner()).to_string(),
+    let input = lines.inner().pop().unwrap().inner();
+
+    if let Some(csv_path) = options.population_csv() {
+        let days = match part {
+            DayPart::One => *parameters.part_1_days(),
+            DayPart::Two => *parameters.part_2_days(),
+        };
+        let series = exit_on_value_error(executor.population_series(input.clone(), days));
+        let rows = series
+            .iter()
+            .enumerate()
+            .map(|(day, population)| [day.to_string(), population.to_string()]);
+        write_csv(csv_path, &["day", "population"], rows).unwrap();
     }
+
+    let population = match part {
+        DayPart::One => executor.part_1(input),
+        DayPart::Two => executor.part_2(input),
+    };
+    exit_on_value_error(population).to_string()
 }
 
-pub(crate) fn run_day_07(part: DayPart, input_path: &Path) -> String {
-    let executor = Day7SolutionExecutor::new();
+fn day_06_parameters(options: &Day6Opt) -> LanternfishParameters {
+    let defaults = LanternfishParameters::default();
+    LanternfishParameters::new(
+        options.reset_timer().unwrap_or(*defaults.reset_timer()),
+        options.newborn_timer().unwrap_or(*defaults.newborn_timer()),
+        options.days().unwrap_or(*defaults.part_1_days()),
+        options.days().unwrap_or(*defaults.part_2_days()),
+        options
+            .growth_engine()
+            .map(Into::into)
+            .unwrap_or(*defaults.engine()),
+    )
+}
+
+pub(crate) fn run_day_07(part: DayPart, input_path: &Path, options: &Day7Opt) -> String {
+    let parameters = day_07_parameters(options);
+    let executor = Day7SolutionExecutor::with_parameters(parameters.clone());
     let lines: Lines<CommaSeparatedList<u64>> = read_input_str(input_path).unwrap();
-    let input = lines.inner().pop().unwrap();
-    match part {
-        DayPart::One => executor.part_1(input.inner()).to_string(),
-        DayPart::Two => executor.part_2(input.inner()).to_string(),
+    let input = lines.inner().pop().unwrap().inner();
+    let fuel_cost = match part {
+        DayPart::One => parameters.part_1_fuel_cost(),
+        DayPart::Two => parameters.part_2_fuel_cost(),
+    };
+
+    if !executor.is_convex(&input, fuel_cost) {
+        eprintln!(
+            "warning: the fuel cost is not convex, so the cheapest position found may be wrong; \
+            check it with --brute-force-check"
+        );
+    }
+
+    if let Some(csv_path) = options.cost_curve_csv() {
+        let rows = exit_on_value_error(executor.cost_curve(input.clone(), fuel_cost))
+            .into_iter()
+            .map(|(position, fuel)| [position.to_string(), fuel.to_string()]);
+        write_csv(csv_path, &["position", "fuel"], rows).unwrap();
+    }
+
+    let alignment = options
+        .alignment()
+        .then(|| exit_on_value_error(executor.alignment(input.clone(), fuel_cost)));
+    let answer = match part {
+        DayPart::One => executor.part_1(input),
+        DayPart::Two => executor.part_2(input),
+    };
+    let answer = exit_on_value_error(answer).to_string();
+
+    match alignment {
+        Some(alignment) => format!("{}\n\n{}", alignment, answer),
+        None => answer,
     }
 }
 
+fn exit_on_value_error<T>(result: Result<T, impl std::fmt::Display>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(error) => Opt::into_app()
+            .error(ErrorKind::ValueValidation, error)
+            .exit(),
+    }
+}
+
+fn day_07_parameters(options: &Day7Opt) -> AlignmentParameters {
+    let defaults = AlignmentParameters::default();
+    let fuel_cost = options.fuel_cost().clone().map(Into::into);
+    AlignmentParameters::new(
+        fuel_cost
+            .clone()
+            .unwrap_or_else(|| defaults.part_1_fuel_cost().clone()),
+        fuel_cost.unwrap_or_else(|| defaults.part_2_fuel_cost().clone()),
+        *options.brute_force_check(),
+    )
+}
+
 pub(crate) fn run_day_08(part: DayPart, input_path: &Path) -> String {
     let executor = Day8SolutionExecutor::new();
     let lines: Lines<SubmarineDisplaySignal> = read_input(input_path).unwrap();
@@ -107,31 +267,147 @@ pub(crate) fn run_day_08(part: DayPart, input_path: &Path) -> String {
     }
 }
 
-pub(crate) fn run_day_09(part: DayPart, input_path: &Path) -> String {
-    let executor = Day9SolutionExecutor::new();
-    let input = read_digit_lines(input_path).unwrap();
-    match part {
-        DayPart::One => executor.part_1(input).to_string(),
-        DayPart::Two => executor.part_2(input).to_string(),
+pub(crate) fn run_day_09(part: DayPart, input_path: &Path, options: &Day9Opt) -> String {
+    let executor = Day9SolutionExecutor::with_parameters(day_09_parameters(options));
+    let input: Grid<u8> = read_input(input_path).unwrap();
+    let input: crate::domain::grid::Grid<u8> = input.into();
+    let answer = match part {
+        DayPart::One => executor.part_1(input.clone()).to_string(),
+        DayPart::Two => executor.part_2(input.clone()).to_string(),
+    };
+
+    if *options.basin_map() {
+        let colour = stdout().is_terminal();
+        let basin_map = executor.basin_map(input).render(|basin, letter| {
+            if colour {
+                format!("\x1b[{}m{}\x1b[0m", 31 + basin.id() % 6, letter)
+            } else {
+                letter.to_string()
+            }
+        });
+        format!("{}\n\n{}", basin_map, answer)
+    } else {
+        answer
     }
 }
 
-pub(crate) fn run_day_10(part: DayPart, input_path: &Path) -> String {
-    let executor = Day10SolutionExecutor::new();
+fn day_09_parameters(options: &Day9Opt) -> TerrainParameters {
+    let defaults = TerrainParameters::default();
+    TerrainParameters::new(
+        options
+            .basin_adjacency()
+            .map(Into::into)
+            .unwrap_or(*defaults.adjacency()),
+        options.wall_height().unwrap_or(*defaults.wall_height()),
+        options
+            .low_points()
+            .map(Into::into)
+            .unwrap_or(*defaults.low_point_rule()),
+    )
+}
+
+pub(crate) fn run_day_10(part: DayPart, input_path: &Path, options: &Day10Opt) -> String {
+    let executor = match options.bracket_pairs() {
+        None => Day10SolutionExecutor::new(),
+        Some(bracket_pairs) => {
+            Day10SolutionExecutor::with_configuration(bracket_pairs.clone().into())
+        }
+    };
+    if *options.streaming() {
+        return run_day_10_streaming(&executor, input_path, options);
+    }
+
     let input: Lines<String> = read_input(input_path).unwrap();
-    match part {
-        DayPart::One => executor.part_1(input.inner()).to_string(),
-        DayPart::Two => executor.part_2(input.inner()).to_string(),
+    let input = input.inner();
+    if *options.repair() {
+        return executor.repair(input).iter().join("\n");
+    }
+
+    let answer = match part {
+        DayPart::One => executor.part_1(input.clone()).to_string(),
+        DayPart::Two => exit_on_value_error(executor.part_2(input.clone())).to_string(),
+    };
+
+    if *options.diagnostics() {
+        executor
+            .diagnostics(input)
+            .iter()
+            .map(ToString::to_string)
+            .chain(std::iter::once(answer))
+            .join("\n\n")
+    } else {
+        answer
     }
 }
 
-pub(crate) fn run_day_11(part: DayPart, input_path: &Path) -> String {
-    let executor = Day11SolutionExecutor::new();
-    let input = read_digit_lines(input_path).unwrap();
-    match part {
-        DayPart::One => executor.part_1(input).to_string(),
-        DayPart::Two => executor.part_2(input).to_string(),
+fn run_day_10_streaming(
+    executor: &Day10SolutionExecutor,
+    input_path: &Path,
+    options: &Day10Opt,
+) -> String {
+    if options
+        .bracket_pairs()
+        .iter()
+        .any(|bracket_pairs| !bracket_pairs.is_ascii())
+    {
+        Opt::into_app()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "streaming only supports ASCII bracket pairs",
+            )
+            .exit()
     }
+
+    let reader = BufReader::new(File::open(input_path).unwrap());
+    exit_on_value_error(executor.check_stream(reader)).to_string()
+}
+
+pub(crate) fn run_day_11(part: DayPart, input_path: &Path, options: &Day11Opt) -> String {
+    let executor = Day11SolutionExecutor::with_parameters(day_11_parameters(options));
+    let input: Grid<u8> = read_input(input_path).unwrap();
+    let input: crate::domain::grid::Grid<u8> = input.into();
+    let answer = match part {
+        DayPart::One => executor.part_1(input.clone()).to_string(),
+        DayPart::Two => exit_on_value_error(executor.part_2(input.clone())).to_string(),
+    };
+
+    match options.steps() {
+        None => answer,
+        Some(steps) => {
+            let steps = steps.clone().inner();
+            let last_step = steps.iter().copied().max().unwrap_or(0);
+            let highlight = stdout().is_terminal();
+            executor
+                .trace(input, last_step)
+                .into_iter()
+                .filter(|trace| steps.contains(trace.step()))
+                .map(|trace| {
+                    trace.render(|energy_level| {
+                        if highlight {
+                            format!("\x1b[1m{}\x1b[0m", energy_level)
+                        } else {
+                            energy_level
+                        }
+                    })
+                })
+                .chain(std::iter::once(answer))
+                .join("\n\n")
+        }
+    }
+}
+
+fn day_11_parameters(options: &Day11Opt) -> SimulationParameters {
+    let defaults = SimulationParameters::default();
+    SimulationParameters::new(
+        options.simulation_steps().unwrap_or(*defaults.steps()),
+        options
+            .flash_threshold()
+            .unwrap_or(*defaults.flash_threshold()),
+        options
+            .flash_adjacency()
+            .map(Into::into)
+            .unwrap_or(*defaults.adjacency()),
+    )
 }
 
 pub(crate) fn run_day_12(part: DayPart, input_path: &Path) -> String {
@@ -0,0 +1,47 @@
+use std::str::FromStr;
+
+use crate::domain::solution_executor::day_07 as domain;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum FuelCost {
+    Linear,
+    Triangular,
+    Quadratic,
+    Cubic,
+    Polynomial(Vec<i64>),
+}
+
+impl FromStr for FuelCost {
+    type Err = ParseFuelCostError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "linear" => Ok(FuelCost::Linear),
+            "triangular" => Ok(FuelCost::Triangular),
+            "quadratic" => Ok(FuelCost::Quadratic),
+            "cubic" => Ok(FuelCost::Cubic),
+            _ => s
+                .split(',')
+                .map(|coefficient| coefficient.trim().parse::<i64>())
+                .collect::<Result<Vec<i64>, _>>()
+                .map(FuelCost::Polynomial)
+                .map_err(|_err| ParseFuelCostError::new(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new)]
+#[error("could not parse fuel cost {0}, expected linear, triangular, quadratic, cubic or comma separated polynomial coefficients")]
+pub(crate) struct ParseFuelCostError(String);
+
+impl From<FuelCost> for domain::FuelCost {
+    fn from(from: FuelCost) -> Self {
+        match from {
+            FuelCost::Linear => domain::FuelCost::Linear,
+            FuelCost::Triangular => domain::FuelCost::Triangular,
+            FuelCost::Quadratic => domain::FuelCost::Polynomial(vec![0, 0, 1]),
+            FuelCost::Cubic => domain::FuelCost::Polynomial(vec![0, 0, 0, 1]),
+            FuelCost::Polynomial(coefficients) => domain::FuelCost::Polynomial(coefficients),
+        }
+    }
+}
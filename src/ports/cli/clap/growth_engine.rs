@@ -0,0 +1,34 @@
+use std::str::FromStr;
+
+use crate::domain::solution_executor::day_06 as domain;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum GrowthEngine {
+    DayByDay,
+    MatrixPower,
+}
+
+impl FromStr for GrowthEngine {
+    type Err = ParseGrowthEngineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day-by-day" => Ok(GrowthEngine::DayByDay),
+            "matrix-power" => Ok(GrowthEngine::MatrixPower),
+            _ => Err(ParseGrowthEngineError::new(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new)]
+#[error("could not parse growth engine {0}, expected day-by-day or matrix-power")]
+pub(crate) struct ParseGrowthEngineError(String);
+
+impl From<GrowthEngine> for domain::GrowthEngine {
+    fn from(from: GrowthEngine) -> Self {
+        match from {
+            GrowthEngine::DayByDay => domain::GrowthEngine::DayByDay,
+            GrowthEngine::MatrixPower => domain::GrowthEngine::MatrixPower,
+        }
+    }
+}
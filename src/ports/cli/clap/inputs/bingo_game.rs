@@ -1,6 +1,8 @@
 use std::num::ParseIntError;
 use std::str::FromStr;
 
+use itertools::Itertools;
+
 use crate::ports::cli::clap::inputs::line_groups::LineGroups;
 
 pub(crate) struct BingoGame {
@@ -29,7 +31,8 @@ impl TryFrom<String> for BingoGame {
             draw_order,
             boards: line_groups[1..]
                 .iter()
-                .map(|group| parse_board(group))
+                .enumerate()
+                .map(|(board_index, group)| parse_board(board_index, group))
                 .collect::<Result<Vec<BingoBoard>, ParseBingoGameError>>()?,
         })
     }
@@ -43,28 +46,39 @@ fn parse_draw_order(s: &str) -> Result<Vec<u8>, ParseBingoGameError> {
         .map_err(ParseBingoGameError::from)
 }
 
-fn parse_board(line_group: &[String]) -> Result<BingoBoard, ParseBingoGameError> {
-    Ok(BingoBoard {
-        rows: line_group
-            .iter()
-            .map(|line| {
-                line.split(' ')
-                    .filter(|s| !s.is_empty())
-                    .map(u8::from_str)
-                    .collect::<Result<Vec<u8>, ParseIntError>>()
-                    .map_err(ParseBingoGameError::from)
-            })
-            .collect::<Result<Vec<Vec<u8>>, ParseBingoGameError>>()?,
-    })
+fn parse_board(
+    board_index: usize,
+    line_group: &[String],
+) -> Result<BingoBoard, ParseBingoGameError> {
+    let rows = line_group
+        .iter()
+        .map(|line| {
+            line.split(' ')
+                .filter(|s| !s.is_empty())
+                .map(u8::from_str)
+                .collect::<Result<Vec<u8>, ParseIntError>>()
+                .map_err(ParseBingoGameError::from)
+        })
+        .collect::<Result<Vec<Vec<u8>>, ParseBingoGameError>>()?;
+
+    if !rows.iter().map(Vec::len).all_equal() {
+        return Err(ParseBingoGameError::InconsistentBoard(board_index));
+    }
+
+    Ok(BingoBoard { rows })
 }
 
-#[derive(Debug, thiserror::Error, derive_new::new)]
-#[error("could not parse bingo game")]
-pub(crate) struct ParseBingoGameError;
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ParseBingoGameError {
+    #[error("could not parse bingo game")]
+    InvalidNumber,
+    #[error("bingo board at index {0} has rows of different lengths")]
+    InconsistentBoard(usize),
+}
 
 impl From<ParseIntError> for ParseBingoGameError {
     fn from(_: ParseIntError) -> Self {
-        ParseBingoGameError::new()
+        ParseBingoGameError::InvalidNumber
     }
 }
 
@@ -76,3 +90,24 @@ impl From<BingoGame> for crate::domain::solution_executor::day_04::BingoGame {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn parses_rectangular_boards() {
+        let game = BingoGame::try_from("1,2\n\n1 2\n3 4\n5 6\n".to_string()).unwrap();
+        assert_that(&game.boards.len()).is_equal_to(1);
+    }
+
+    #[test]
+    fn names_board_with_inconsistent_row_lengths() {
+        let result = BingoGame::try_from("1,2\n\n1 2\n3 4\n\n1 2\n3\n".to_string());
+        assert_that(&result.err().map(|error| error.to_string())).is_equal_to(Some(
+            "bingo board at index 1 has rows of different lengths".to_string(),
+        ));
+    }
+}
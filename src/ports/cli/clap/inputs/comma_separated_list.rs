@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct CommaSeparatedList<T> {
     inner: Vec<T>,
 }
@@ -0,0 +1,183 @@
+use crate::domain::grid as domain;
+
+/// A single cell of a character grid, parsed from one input character.
+pub(crate) trait GridCell: Sized {
+    fn from_char(character: char) -> Option<Self>;
+}
+
+impl GridCell for u8 {
+    fn from_char(character: char) -> Option<Self> {
+        character.to_digit(10).map(|digit| digit as u8)
+    }
+}
+
+impl GridCell for bool {
+    fn from_char(character: char) -> Option<Self> {
+        match character {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Grid<T> {
+    rows: Vec<Vec<T>>,
+}
+
+impl<T: GridCell> TryFrom<String> for Grid<T> {
+    type Error = ParseGridError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let numbered_rows = value
+            .lines()
+            .enumerate()
+            .filter(|(_row_index, line)| !line.is_empty())
+            .map(|(row_index, line)| parse_row(row_index, line).map(|row| (row_index, row)))
+            .collect::<Result<Vec<(usize, Vec<T>)>, ParseGridError>>()?;
+
+        if let Some(expected) = numbered_rows.first().map(|(_row_index, row)| row.len()) {
+            if let Some((row_index, row)) = numbered_rows
+                .iter()
+                .find(|(_row_index, row)| row.len() != expected)
+            {
+                return Err(ParseGridError::RaggedRow {
+                    row: row_index + 1,
+                    expected,
+                    found: row.len(),
+                });
+            }
+        }
+
+        let rows = numbered_rows
+            .into_iter()
+            .map(|(_row_index, row)| row)
+            .collect();
+        Ok(Grid { rows })
+    }
+}
+
+fn parse_row<T: GridCell>(row_index: usize, line: &str) -> Result<Vec<T>, ParseGridError> {
+    line.chars()
+        .enumerate()
+        .map(|(col_index, character)| {
+            T::from_char(character).ok_or(ParseGridError::InvalidCell {
+                row: row_index + 1,
+                col: col_index + 1,
+                character,
+            })
+        })
+        .collect()
+}
+
+/// Rows and columns are reported counting from 1, as they appear in the input file.
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub(crate) enum ParseGridError {
+    #[error("grid row {row} has {found} cells, expected {expected}")]
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("invalid grid cell '{character}' at row {row}, column {col}")]
+    InvalidCell {
+        row: usize,
+        col: usize,
+        character: char,
+    },
+}
+
+impl<T> From<Grid<T>> for domain::Grid<T> {
+    fn from(from: Grid<T>) -> Self {
+        domain::Grid::new(from.rows).expect("grid rows were not validated")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[derive(Debug, Eq, PartialEq)]
+    enum Herd {
+        East,
+        South,
+        Empty,
+    }
+
+    impl GridCell for Herd {
+        fn from_char(character: char) -> Option<Self> {
+            match character {
+                '>' => Some(Herd::East),
+                'v' => Some(Herd::South),
+                '.' => Some(Herd::Empty),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn parses_digit_grid() {
+        let grid: Grid<u8> = "12\n34\n".to_string().try_into().unwrap();
+        assert_that(&grid.rows).is_equal_to(vec![vec![1, 2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn parses_bitmap_grid() {
+        let grid: Grid<bool> = "#.\n.#".to_string().try_into().unwrap();
+        assert_that(&grid.rows).is_equal_to(vec![vec![true, false], vec![false, true]]);
+    }
+
+    #[test]
+    fn parses_symbol_grid() {
+        let grid: Grid<Herd> = ">v.".to_string().try_into().unwrap();
+        assert_that(&grid.rows).is_equal_to(vec![vec![Herd::East, Herd::South, Herd::Empty]]);
+    }
+
+    #[test]
+    fn reports_position_of_invalid_cell() {
+        let result: Result<Grid<u8>, ParseGridError> = "12\n3x".to_string().try_into();
+        assert_that(&result.unwrap_err()).is_equal_to(ParseGridError::InvalidCell {
+            row: 2,
+            col: 2,
+            character: 'x',
+        });
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        let result: Result<Grid<u8>, ParseGridError> = "123\n45\n678".to_string().try_into();
+        assert_that(&result.unwrap_err()).is_equal_to(ParseGridError::RaggedRow {
+            row: 2,
+            expected: 3,
+            found: 2,
+        });
+    }
+
+    #[test]
+    fn counts_blank_lines_in_reported_rows() {
+        let result: Result<Grid<u8>, ParseGridError> = "12
+
+3x"
+        .to_string()
+        .try_into();
+        assert_that(&result.unwrap_err()).is_equal_to(ParseGridError::InvalidCell {
+            row: 3,
+            col: 2,
+            character: 'x',
+        });
+
+        let result: Result<Grid<u8>, ParseGridError> = "123
+
+45"
+        .to_string()
+        .try_into();
+        assert_that(&result.unwrap_err()).is_equal_to(ParseGridError::RaggedRow {
+            row: 3,
+            expected: 3,
+            found: 2,
+        });
+    }
+}
@@ -2,6 +2,7 @@ pub(crate) mod bingo_game;
 pub(crate) mod cave_connection;
 pub(crate) mod comma_separated_list;
 pub(crate) mod direction_and_size;
+pub(crate) mod grid;
 pub(crate) mod line_groups;
 pub(crate) mod lines;
 pub(crate) mod origami_instructions;
@@ -0,0 +1,34 @@
+use std::str::FromStr;
+
+use crate::domain::solution_executor::day_09 as domain;
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum LowPointRule {
+    Strict,
+    Plateau,
+}
+
+impl FromStr for LowPointRule {
+    type Err = ParseLowPointRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(LowPointRule::Strict),
+            "plateau" => Ok(LowPointRule::Plateau),
+            _ => Err(ParseLowPointRuleError::new(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new)]
+#[error("could not parse low point rule {0}, expected strict or plateau")]
+pub(crate) struct ParseLowPointRuleError(String);
+
+impl From<LowPointRule> for domain::LowPointRule {
+    fn from(from: LowPointRule) -> Self {
+        match from {
+            LowPointRule::Strict => domain::LowPointRule::Strict,
+            LowPointRule::Plateau => domain::LowPointRule::Plateau,
+        }
+    }
+}
@@ -1,8 +1,17 @@
 use std::path::PathBuf;
 
-use clap::Parser;
+use clap::{Args, Parser};
 
+use crate::ports::cli::clap::adjacency::Adjacency;
+use crate::ports::cli::clap::bracket_pair::BracketPairs;
 use crate::ports::cli::clap::day_part::DayPart;
+use crate::ports::cli::clap::fuel_cost::FuelCost;
+use crate::ports::cli::clap::growth_engine::GrowthEngine;
+use crate::ports::cli::clap::inputs::comma_separated_list::CommaSeparatedList;
+use crate::ports::cli::clap::low_point_rule::LowPointRule;
+use crate::ports::cli::clap::overlap_engine::OverlapEngine;
+use crate::ports::cli::clap::rasterisation::Rasterisation;
+use crate::ports::cli::clap::win_rule::WinRule;
 
 /// Executor of the 2021 Advent of Code challenge solutions
 #[derive(Parser, Debug, derive_getters::Getters)]
@@ -19,4 +28,162 @@ pub(crate) struct Opt {
     /// Challenge part
     #[structopt(short, long, default_value = "1")]
     part: DayPart,
+
+    #[structopt(flatten)]
+    day_04: Day4Opt,
+
+    #[structopt(flatten)]
+    day_05: Day5Opt,
+
+    #[structopt(flatten)]
+    day_06: Day6Opt,
+
+    #[structopt(flatten)]
+    day_07: Day7Opt,
+
+    #[structopt(flatten)]
+    day_09: Day9Opt,
+
+    #[structopt(flatten)]
+    day_10: Day10Opt,
+
+    #[structopt(flatten)]
+    day_11: Day11Opt,
+}
+
+#[derive(Args, Debug, derive_getters::Getters)]
+pub(crate) struct Day4Opt {
+    /// Day 4: print when every board won, and with what score, before the answer
+    #[clap(long)]
+    leaderboard: bool,
+
+    /// Day 4: patterns that win a board, any of lines, diagonals, corners or full-card [default: lines]
+    #[clap(long)]
+    win_rules: Option<CommaSeparatedList<WinRule>>,
+}
+
+#[derive(Args, Debug, derive_getters::Getters)]
+pub(crate) struct Day5Opt {
+    /// Day 5: how lines that are not horizontal, vertical or 45 degrees are drawn, lattice or bresenham [default: lattice]
+    #[clap(long)]
+    rasterisation: Option<Rasterisation>,
+
+    /// Day 5: how overlaps are counted, rasterised or analytic (lattice rasterisation only, for huge coordinates) [default: rasterised]
+    #[clap(long)]
+    overlap_engine: Option<OverlapEngine>,
+
+    /// Day 5: number of lines that must cover a point for it to count as overlapping [default: 2]
+    #[clap(long)]
+    overlap_threshold: Option<usize>,
+
+    /// Day 5: print how many points are covered by each number of lines, and the most overlapped point, before the answer
+    #[clap(long)]
+    overlap_report: bool,
+
+    /// Day 5: write a heatmap of line overlaps to this path, colour for .ppm and greyscale otherwise (at most 16777216 points)
+    #[clap(long, parse(from_os_str))]
+    heatmap: Option<PathBuf>,
+
+    /// Day 5: only draw horizontal and vertical lines in the heatmap, as part 1 does
+    #[clap(long)]
+    heatmap_axis_aligned: bool,
+}
+
+#[derive(Args, Debug, derive_getters::Getters)]
+pub(crate) struct Day6Opt {
+    /// Day 6: how the population is grown, day-by-day or matrix-power (for huge day counts) [default: day-by-day]
+    #[clap(long)]
+    growth_engine: Option<GrowthEngine>,
+
+    /// Day 6: timer a lanternfish restarts from after giving birth [default: 6]
+    #[clap(long)]
+    reset_timer: Option<u8>,
+
+    /// Day 6: timer a newborn lanternfish starts from [default: 8]
+    #[clap(long)]
+    newborn_timer: Option<u8>,
+
+    /// Day 6: number of days simulated [default: 80 for part 1, 256 for part 2]
+    #[clap(long)]
+    days: Option<usize>,
+
+    /// Day 6: write the population at the start and after each day to this CSV file
+    #[clap(long, parse(from_os_str))]
+    population_csv: Option<PathBuf>,
+}
+
+#[derive(Args, Debug, derive_getters::Getters)]
+pub(crate) struct Day7Opt {
+    /// Day 7: fuel used moving a crab, linear, triangular, quadratic, cubic or polynomial coefficients from the constant term up (e.g. 0,1,1) [default: linear for part 1, triangular for part 2]
+    #[clap(long)]
+    fuel_cost: Option<FuelCost>,
+
+    /// Day 7: check the answer against the fuel used at every position between the outermost crabs
+    #[clap(long)]
+    brute_force_check: bool,
+
+    /// Day 7: print the cheapest positions to align at before the answer
+    #[clap(long)]
+    alignment: bool,
+
+    /// Day 7: write the fuel used aligning at each position to this CSV file
+    #[clap(long, parse(from_os_str))]
+    cost_curve_csv: Option<PathBuf>,
+}
+
+#[derive(Args, Debug, derive_getters::Getters)]
+pub(crate) struct Day9Opt {
+    /// Day 9: print the map with each basin drawn as a letter, and a description of each basin, before the answer
+    #[clap(long)]
+    basin_map: bool,
+
+    /// Day 9: neighbours a basin flows between and a low point is compared with, 4 or 8 [default: 4]
+    #[clap(long)]
+    basin_adjacency: Option<Adjacency>,
+
+    /// Day 9: locations at least this high are walls between basins [default: 9]
+    #[clap(long)]
+    wall_height: Option<u8>,
+
+    /// Day 9: whether a low point must be lower than its neighbours, strict, or no higher, plateau [default: strict]
+    #[clap(long)]
+    low_points: Option<LowPointRule>,
+}
+
+#[derive(Args, Debug, derive_getters::Getters)]
+pub(crate) struct Day10Opt {
+    /// Day 10: print each corrupted line with a caret under its first illegal character before the answer
+    #[clap(long)]
+    diagnostics: bool,
+
+    /// Day 10: bracket pairs with their error and completion scores (e.g. ():3:1,<>:25137:4) [default: ():3:1,[]:57:2,{}:1197:3,<>:25137:4]
+    #[clap(long)]
+    bracket_pairs: Option<BracketPairs>,
+
+    /// Day 10: check the input as it is read, without loading it all, and print the answers to both parts, keeping one number per incomplete line (ASCII bracket pairs only)
+    #[clap(long, conflicts_with = "diagnostics")]
+    streaming: bool,
+
+    /// Day 10: print each line with its completion appended, and each corrupted line or line with an unknown character flagged with a leading #, instead of the answer
+    #[clap(long, conflicts_with_all = &["diagnostics", "streaming"])]
+    repair: bool,
+}
+
+#[derive(Args, Debug, derive_getters::Getters)]
+pub(crate) struct Day11Opt {
+    /// Day 11: print the octopus energy levels after each of these steps (e.g. 1,2,10,100)
+    #[clap(long)]
+    steps: Option<CommaSeparatedList<usize>>,
+
+    /// Day 11: number of steps simulated for part 1, separate from the steps printed with --steps [default: 100]
+    #[clap(long)]
+    simulation_steps: Option<usize>,
+
+    /// Day 11: energy level an octopus must exceed to flash [default: 9]
+    #[clap(long)]
+    flash_threshold: Option<u8>,
+
+    /// Day 11: neighbours raised by a flash, 4 or 8 [default: 8]
+    #[clap(long)]
+    flash_adjacency: Option<Adjacency>,
 }
@@ -0,0 +1,41 @@
+use std::fs::write;
+use std::io;
+use std::path::Path;
+
+use itertools::Itertools;
+
+/// Writes a header line followed by one line per row, with fields separated by commas.
+pub(crate) fn write_csv<R: IntoIterator<Item = String>>(
+    path: &Path,
+    header: &[&str],
+    rows: impl IntoIterator<Item = R>,
+) -> io::Result<()> {
+    write(path, encode(header, rows))
+}
+
+fn encode<R: IntoIterator<Item = String>>(
+    header: &[&str],
+    rows: impl IntoIterator<Item = R>,
+) -> String {
+    std::iter::once(header.join(","))
+        .chain(rows.into_iter().map(|row| row.into_iter().join(",")))
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn encodes_header_and_rows() {
+        let rows = vec![
+            vec!["0".to_string(), "5".to_string()],
+            vec!["1".to_string(), "6".to_string()],
+        ];
+        assert_that(&encode(&["day", "population"], rows))
+            .is_equal_to("day,population\n0,5\n1,6\n".to_string());
+    }
+}
@@ -0,0 +1,96 @@
+use std::fs::write;
+use std::io;
+use std::path::Path;
+
+use crate::domain::grid::Grid;
+
+/// Netpbm image formats, which need nothing more than a header and raw pixel bytes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ImageFormat {
+    /// Greyscale PGM
+    Greymap,
+    /// Colour PPM
+    Pixmap,
+}
+
+impl ImageFormat {
+    /// Writes colour for a `.ppm` extension and greyscale otherwise.
+    pub(crate) fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("ppm") => ImageFormat::Pixmap,
+            _ => ImageFormat::Greymap,
+        }
+    }
+}
+
+/// Writes a heatmap of `values`, scaled so the largest value is the brightest pixel.
+pub(crate) fn write_heatmap(path: &Path, values: &Grid<usize>) -> io::Result<()> {
+    write(path, encode(values, ImageFormat::from_path(path)))
+}
+
+fn encode(values: &Grid<usize>, format: ImageFormat) -> Vec<u8> {
+    let magic_number = match format {
+        ImageFormat::Greymap => "P5",
+        ImageFormat::Pixmap => "P6",
+    };
+    let mut image = format!(
+        "{}\n{} {}\n255\n",
+        magic_number,
+        values.width(),
+        values.height()
+    )
+    .into_bytes();
+
+    let max_value = values.values().copied().max().unwrap_or(0).max(1);
+    values.values().for_each(|value| {
+        let intensity = (value * 255 / max_value) as u8;
+        match format {
+            ImageFormat::Greymap => image.push(intensity),
+            ImageFormat::Pixmap => image.extend(heat_colour(intensity)),
+        }
+    });
+    image
+}
+
+/// Runs from black through red to yellow as intensity rises.
+fn heat_colour(intensity: u8) -> [u8; 3] {
+    let doubled = intensity as usize * 2;
+    if doubled <= 255 {
+        [doubled as u8, 0, 0]
+    } else {
+        [255, (doubled - 255) as u8, 0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use speculoos::prelude::*;
+
+    use super::*;
+
+    fn test_values() -> Grid<usize> {
+        Grid::new(vec![vec![0, 1], vec![2, 4]]).unwrap()
+    }
+
+    #[test]
+    fn encodes_greymap() {
+        let mut expected = b"P5\n2 2\n255\n".to_vec();
+        expected.extend([0, 63, 127, 255]);
+        assert_that(&encode(&test_values(), ImageFormat::Greymap)).is_equal_to(expected);
+    }
+
+    #[test]
+    fn encodes_pixmap() {
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend([0, 0, 0, 126, 0, 0, 254, 0, 0, 255, 255, 0]);
+        assert_that(&encode(&test_values(), ImageFormat::Pixmap)).is_equal_to(expected);
+    }
+
+    #[test]
+    fn chooses_format_from_extension() {
+        assert_that(&ImageFormat::from_path(Path::new("vents.ppm")))
+            .is_equal_to(ImageFormat::Pixmap);
+        assert_that(&ImageFormat::from_path(Path::new("vents.pgm")))
+            .is_equal_to(ImageFormat::Greymap);
+    }
+}
@@ -0,0 +1,2 @@
+pub(crate) mod csv;
+pub(crate) mod heatmap;
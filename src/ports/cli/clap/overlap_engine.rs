@@ -0,0 +1,34 @@
+use std::str::FromStr;
+
+use crate::domain::solution_executor::day_05 as domain;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum OverlapEngine {
+    Rasterised,
+    Analytic,
+}
+
+impl FromStr for OverlapEngine {
+    type Err = ParseOverlapEngineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rasterised" => Ok(OverlapEngine::Rasterised),
+            "analytic" => Ok(OverlapEngine::Analytic),
+            _ => Err(ParseOverlapEngineError::new(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new)]
+#[error("could not parse overlap engine {0}, expected rasterised or analytic")]
+pub(crate) struct ParseOverlapEngineError(String);
+
+impl From<OverlapEngine> for domain::OverlapEngine {
+    fn from(from: OverlapEngine) -> Self {
+        match from {
+            OverlapEngine::Rasterised => domain::OverlapEngine::Rasterised,
+            OverlapEngine::Analytic => domain::OverlapEngine::Analytic,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::str::FromStr;
+
+use crate::domain::solution_executor::day_05 as domain;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Rasterisation {
+    LatticePoints,
+    Bresenham,
+}
+
+impl FromStr for Rasterisation {
+    type Err = ParseRasterisationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lattice" => Ok(Rasterisation::LatticePoints),
+            "bresenham" => Ok(Rasterisation::Bresenham),
+            _ => Err(ParseRasterisationError::new(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new)]
+#[error("could not parse rasterisation {0}, expected lattice or bresenham")]
+pub(crate) struct ParseRasterisationError(String);
+
+impl From<Rasterisation> for domain::Rasterisation {
+    fn from(from: Rasterisation) -> Self {
+        match from {
+            Rasterisation::LatticePoints => domain::Rasterisation::LatticePoints,
+            Rasterisation::Bresenham => domain::Rasterisation::Bresenham,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::str::FromStr;
+
+use crate::domain::solution_executor::day_04 as domain;
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum WinRule {
+    Lines,
+    Diagonals,
+    Corners,
+    FullCard,
+}
+
+impl FromStr for WinRule {
+    type Err = ParseWinRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lines" => Ok(WinRule::Lines),
+            "diagonals" => Ok(WinRule::Diagonals),
+            "corners" => Ok(WinRule::Corners),
+            "full-card" => Ok(WinRule::FullCard),
+            _ => Err(ParseWinRuleError::new(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, derive_new::new)]
+#[error("could not parse win rule {0}, expected lines, diagonals, corners or full-card")]
+pub(crate) struct ParseWinRuleError(String);
+
+impl From<WinRule> for Box<dyn domain::WinRule> {
+    fn from(from: WinRule) -> Self {
+        match from {
+            WinRule::Lines => Box::new(domain::RowOrColumnRule::new()),
+            WinRule::Diagonals => Box::new(domain::DiagonalRule::new()),
+            WinRule::Corners => Box::new(domain::FourCornersRule::new()),
+            WinRule::FullCard => Box::new(domain::FullCardRule::new()),
+        }
+    }
+}